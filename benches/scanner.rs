@@ -0,0 +1,66 @@
+/* Date Created: 18/10/2026. */
+
+//! Scanner throughput, over all scripts in `./tests/data/`, and over a 
+//! generated script of about 1MB.
+//!
+//! To run:
+//!
+//!     * cargo bench --bench scanner
+
+use std::fs::{read_dir, read_to_string};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use rlox::scanner::Scanner;
+
+const ROUNDS: usize = 20;
+
+fn collect_scripts(dir: &Path, scripts: &mut Vec<String>) {
+    for entry in read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_scripts(&path, scripts);
+        } else if path.extension().is_some_and(|ext| ext == "lox") {
+            scripts.push(read_to_string(&path).unwrap());
+        }
+    }
+}
+
+// Repeats a function with comments, strings and non-ASCII text.
+fn generated_script(size: usize) -> String {
+    let chunk = "// Größe: the sum of a list.\n\
+        fun sum(list) {\n  var total = 0.5;\n  for (var i = 0; i < list.len(); i = i + 1) {\n    \
+        total = total + list[i];\n  }\n  print \"Σ = \" ;\n  return total >= 10 and total != 42;\n}\n";
+    chunk.repeat(size / chunk.len() + 1)
+}
+
+// Scans all of `sources` ROUNDS times: returns the tokens and the time taken.
+fn scan_all(sources: &[String]) -> (usize, Duration) {
+    let start = Instant::now();
+    let mut tokens = 0;
+    for _ in 0..ROUNDS {
+        for source in sources {
+            // Some scripts test scanner errors.
+            if let Ok(scanned) = Scanner::new(source).scan_tokens() {
+                tokens += scanned.len();
+            }
+        }
+    }
+    (tokens, start.elapsed())
+}
+
+fn report(name: &str, sources: &[String]) {
+    let bytes: usize = sources.iter().map(|source| source.len()).sum::<usize>() * ROUNDS;
+    let (tokens, elapsed) = scan_all(sources);
+    let seconds = elapsed.as_secs_f64();
+    println!("{:<10} {:>9} bytes {:>9} tokens {:>9.3} s {:>8.2} MB/s {:>12.0} tokens/s", 
+        name, bytes, tokens, seconds, bytes as f64 / seconds / 1e6, tokens as f64 / seconds);
+}
+
+fn main() {
+    let mut scripts = vec![];
+    collect_scripts(Path::new("./tests/data"), &mut scripts);
+
+    report("tests/data", &scripts);
+    report("generated", &[generated_script(1024 * 1024)]);
+}
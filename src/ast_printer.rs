@@ -20,6 +20,26 @@ use super::expr;
 use super::stmt;
 use super::{unwrap_expr, unwrap_stmt};
 
+// Not in the book: a string literal as it is written in Lox, quoted, with 
+// escape sequences. See Scanner::escape().
+fn escape_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            '\0' => escaped.push_str("\\0"),
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:X}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
 // Rust-specific.
 pub enum AstFragment<'a> {
     Expr(Rc<expr::Expr>),
@@ -165,12 +185,33 @@ impl expr::Visitor<String> for AstPrinter {
         Ok(self.parenthesize("group", &[Rc::clone(grouping.expression())])?)
     }
 
+    fn visit_index_expr(&mut self, expr: Rc<expr::Expr>) -> Result<String, LoxRuntimeError> {
+        let index = unwrap_expr!(expr, Index);
+
+        Ok(self.parenthesize("index", 
+            &[Rc::clone(index.object()), Rc::clone(index.index())])?)
+    }
+
+    fn visit_indexset_expr(&mut self, expr: Rc<expr::Expr>) -> Result<String, LoxRuntimeError> {
+        let index_set = unwrap_expr!(expr, IndexSet);
+
+        Ok(self.parenthesize("index=", 
+            &[Rc::clone(index_set.object()), Rc::clone(index_set.index()), 
+              Rc::clone(index_set.value())])?)
+    }
+
+    fn visit_list_expr(&mut self, expr: Rc<expr::Expr>) -> Result<String, LoxRuntimeError> {
+        let list = unwrap_expr!(expr, List);
+
+        Ok(self.parenthesize("list", list.elements())?)
+    }
+
     fn visit_literal_expr(&mut self, expr: Rc<expr::Expr>) -> Result<String, LoxRuntimeError> {
         let literal = unwrap_expr!(expr, Literal);
 
         match literal.value() {
             LiteralValue::Number(n) => Ok(format!("{:?}", n)),
-            LiteralValue::String(s) => Ok(s.to_string()),
+            LiteralValue::String(s) => Ok(escape_string(s)),
             LiteralValue::Boolean(b) => Ok(b.to_string()),
             LiteralValue::Nil => Ok("nil".to_string()),
         }        
@@ -189,6 +230,18 @@ impl expr::Visitor<String> for AstPrinter {
         )?)
     }
 
+    fn visit_map_expr(&mut self, expr: Rc<expr::Expr>) -> Result<String, LoxRuntimeError> {
+        let map = unwrap_expr!(expr, Map);
+
+        let entries: Vec<Rc<expr::Expr>> = map.keys()
+            .iter()
+            .zip(map.values())
+            .flat_map(|(key, value)| [Rc::clone(key), Rc::clone(value)])
+            .collect();
+
+        Ok(self.parenthesize("map", &entries)?)
+    }
+
     // My note: untested.
     fn visit_set_expr(&mut self, expr: Rc<expr::Expr>) -> Result<String, LoxRuntimeError> {
         let set = unwrap_expr!(expr, Set);
@@ -252,6 +305,10 @@ impl stmt::Visitor<String> for AstPrinter {
         Ok(self.parenthesize2("block", &fragments)?)
     }
 
+    fn visit_break_stmt(&mut self, _: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
+        Ok("(break)".to_string())
+    }
+
     // My note: untested.
     fn visit_class_stmt(&mut self, stmt: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
         let class = unwrap_stmt!(stmt, Class);
@@ -275,6 +332,10 @@ impl stmt::Visitor<String> for AstPrinter {
         Ok(builder)
     }
 
+    fn visit_continue_stmt(&mut self, _: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
+        Ok("(continue)".to_string())
+    }
+
     // My note: untested.
     fn visit_expression_stmt(&mut self, stmt: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
         let expression = unwrap_stmt!(stmt, Expression);
@@ -358,6 +419,46 @@ impl stmt::Visitor<String> for AstPrinter {
         }
     }
 
+    fn visit_import_stmt(&mut self, stmt: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
+        let import = unwrap_stmt!(stmt, Import);
+
+        Ok(self.parenthesize2("import", &[
+            AstFragment::Text(format!("\"{}\"", import.path())),
+            AstFragment::Token(import.name()),
+        ])?)
+    }
+
+    fn visit_throw_stmt(&mut self, stmt: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Throw);
+
+        Ok(self.parenthesize2("throw", &[AstFragment::Expr(Rc::clone(inner.value()))])?)
+    }
+
+    fn visit_try_stmt(&mut self, stmt: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Try);
+
+        let to_fragments = |statements: &Vec<Rc<stmt::Stmt>>| -> Vec<AstFragment> {
+            statements.iter().map(|s| AstFragment::Stmt(Rc::clone(s))).collect()
+        };
+
+        let mut fragments = vec![
+            AstFragment::Text(self.parenthesize2("block", &to_fragments(inner.body()))?),
+        ];
+
+        if let (Some(name), Some(catch_body)) = (inner.catch_name(), inner.catch_body()) {
+            let mut catch_fragments = vec![AstFragment::Token(name)];
+            catch_fragments.extend(to_fragments(catch_body));
+            fragments.push(AstFragment::Text(self.parenthesize2("catch", &catch_fragments)?));
+        }
+
+        if let Some(finally_body) = inner.finally_body() {
+            fragments.push(AstFragment::Text(
+                self.parenthesize2("finally", &to_fragments(finally_body))?));
+        }
+
+        Ok(self.parenthesize2("try", &fragments)?)
+    }
+
     // My note: untested.
     fn visit_var_stmt(&mut self, stmt: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
         let var = unwrap_stmt!(stmt, Var);
@@ -378,13 +479,16 @@ impl stmt::Visitor<String> for AstPrinter {
     fn visit_while_stmt(&mut self, stmt: Rc<stmt::Stmt>) -> Result<String, LoxRuntimeError> {
         let inner = unwrap_stmt!(stmt, While);
 
-        Ok(self.parenthesize2(
-            "while",
-            &[
-                AstFragment::Expr(Rc::clone(inner.condition())),
-                AstFragment::Stmt(inner.body().clone()),
-            ],
-        )?)
+        let mut fragments = vec![
+            AstFragment::Expr(Rc::clone(inner.condition())),
+            AstFragment::Stmt(inner.body().clone()),
+        ];
+
+        if let Some(increment) = inner.increment() {
+            fragments.push(AstFragment::Expr(Rc::clone(increment)));
+        }
+
+        Ok(self.parenthesize2("while", &fragments)?)
     }
 }
 
@@ -392,7 +496,7 @@ impl stmt::Visitor<String> for AstPrinter {
 mod tests {
     use std::rc::Rc;
     use crate::token_type::TokenType;
-    use crate::token::{LiteralValue, Token};
+    use crate::token::{LiteralValue, Span, Token};
     use super::expr;
     use super::expr::*;
     use crate::ast_printer::AstPrinter;
@@ -404,14 +508,26 @@ mod tests {
         let expr = Rc::new(expr::Expr::Binary(Binary::new(
             Rc::new(expr::Expr::Unary(Unary::new(
                 Token::new(TokenType::Minus, "-".to_string(), None, 1),
-                Rc::new(expr::Expr::Literal(Literal::new(LiteralValue::Number(123.0)))),
+                Rc::new(expr::Expr::Literal(Literal::new(LiteralValue::Number(123.0), Span::default()))),
+                Span::default(),
             ))),
             Token::new(TokenType::Star, "*".to_string(), None, 1),
             Rc::new(expr::Expr::Grouping(Grouping::new(
-                Rc::new(expr::Expr::Literal(Literal::new(LiteralValue::Number(45.67)))),
+                Rc::new(expr::Expr::Literal(Literal::new(LiteralValue::Number(45.67), Span::default()))),
+                Span::default(),
             ))),
+            Span::default(),
         )));
 
         assert_eq!("(* (- 123.0) (group 45.67))".to_string(), AstPrinter{}.print_expression(expr).unwrap());
     }
+
+    #[test]
+    // Strings print as Lox literals: the scanner reads them back unchanged.
+    fn strings_are_escaped() {
+        let expr = Rc::new(expr::Expr::Literal(Literal::new(
+            LiteralValue::String("say \"hi\"\n\tC:\\ \0 \u{1b} ☃".to_string()), Span::default())));
+
+        assert_eq!(r#""say \"hi\"\n\tC:\\ \0 \u{1B} ☃""#, AstPrinter{}.print_expression(expr).unwrap());
+    }
 }
@@ -0,0 +1,43 @@
+/* Date Created: 18/10/2026. */
+
+//! Rust-specific. Cancels a running script from another thread: the 
+//! interpreter checks the token on every loop iteration and every call, and 
+//! stops the script with a "Script cancelled." error. See 
+//! `Interpreter::with_cancel_token()`.
+//!
+//! ```
+//! use rlox::cancel_token::CancelToken;
+//!
+//! let token = CancelToken::new();
+//! let handle = token.clone();
+//! std::thread::spawn(move || handle.cancel()).join().unwrap();
+//! assert!(token.is_cancelled());
+//! ```
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// So that scripts can run again.
+    #[allow(dead_code)]
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,177 @@
+/* Date Created: 17/10/2026. */
+
+//! Rust-specific. Renders [`LoxError`]s rustc-style: the file name, line and
+//! column, the offending source line, a caret underline under the exact token,
+//! the help note if there is one, and the stack trace of a runtime error. E.g.:
+//!
+//! ```text
+//! Parser error: Invalid assignment target.
+//!  --> ./tests/data/diagnostics/invalid_assignment.lox:2:7
+//!   |
+//! 2 | a + b = c;
+//!   |       ^
+//!   = help: only variables, properties and list or map elements can be assigned to.
+//! ```
+//!
+//! Errors without a span, such as uncaught exceptions, are rendered as their
+//! message only. A runtime error in an imported module is rendered against 
+//! that module's file, without the source line if it can't be read.
+
+use std::env::current_dir;
+use std::fs::read_to_string;
+use std::io::IsTerminal;
+use std::path::Path;
+
+use super::lox_error::LoxError;
+
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const BLUE: &str = "\x1b[1;34m";
+const RESET: &str = "\x1b[0m";
+
+/// The `--color=always|never|auto` command line option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorChoice {
+    Always,
+    Never,
+    Auto,
+}
+
+impl ColorChoice {
+    pub fn parse(value: &str) -> Option<ColorChoice> {
+        match value {
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            "auto" => Some(ColorChoice::Auto),
+            _ => None,
+        }
+    }
+
+    /// `Auto` colours only when stderr, where errors go, is a terminal.
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => std::io::stderr().is_terminal(),
+        }
+    }
+}
+
+pub struct Diagnostics<'a> {
+    source: &'a str,
+    file_name: &'a str,
+    color: bool,
+}
+
+impl<'a> Diagnostics<'a> {
+    pub fn new(source: &'a str, file_name: &'a str, color: bool) -> Self {
+        Diagnostics { source, file_name, color }
+    }
+
+    // Wraps `text` in an ANSI colour, when colours are on.
+    fn paint(&self, colour: &str, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", colour, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Renders every error `err` collects, one block each, separated by a blank
+    /// line. `originator` is the phase: "Scanner", "Parser", "Resolver", etc.
+    pub fn render(&self, originator: &str, err: &LoxError) -> String {
+        err.errors()
+            .iter()
+            .map(|err| self.render_error(originator, err))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn render_error(&self, originator: &str, err: &LoxError) -> String {
+        let span = err.span();
+        let line = if span.line > 0 { span.line } else { err.line() };
+
+        let mut out = String::new();
+
+        let message = if line > 0 { err.err_msg().to_string() } else { err.to_string() };
+        out.push_str(&format!("{}{}\n", self.paint(RED, &format!("{} error", originator)),
+            self.paint(BOLD, &format!(": {}", message))));
+
+        if line > 0 {
+            let width = line.to_string().len();
+            let pad = " ".repeat(width);
+
+            // The error's own file: the script, or a module.
+            let module_source: Option<String>;
+            let (source, file_name) = match err.source_path() {
+                Some(path) => {
+                    module_source = read_to_string(path).ok();
+                    (module_source.as_deref(), Self::display_path(path))
+                }
+                None => (Some(self.source), self.file_name.to_string()),
+            };
+
+            let location = if span.line > 0 {
+                format!("{}:{}:{}", file_name, line, span.column)
+            } else {
+                format!("{}:{}", file_name, line)
+            };
+            out.push_str(&format!("{}{} {}\n", pad, self.paint(BLUE, "-->"), location));
+
+            if let Some(text) = source.and_then(|source| source.lines().nth(line - 1)) {
+                out.push_str(&format!("{} {}\n", pad, self.paint(BLUE, "|")));
+                out.push_str(&format!("{} {}\n",
+                    self.paint(BLUE, &format!("{:>width$} |", line, width = width)), text));
+
+                if span.line > 0 {
+                    out.push_str(&format!("{} {} {}\n", pad, self.paint(BLUE, "|"),
+                        self.underline(text, err)));
+                }
+            }
+
+            if let Some(help) = err.help() {
+                out.push_str(&format!("{} {} {}: {}\n", pad, self.paint(BLUE, "="),
+                    self.paint(BOLD, "help"), help));
+            }
+        } else if let Some(help) = err.help() {
+            out.push_str(&format!("{}: {}\n", self.paint(BOLD, "help"), help));
+        }
+
+        for frame in err.trace() {
+            out.push_str(&format!("{}\n", frame));
+        }
+
+        out
+    }
+
+    // Module paths are canonical: shown relative to the current directory when 
+    // they are in it.
+    fn display_path(path: &Path) -> String {
+        current_dir().ok()
+            .and_then(|dir| path.strip_prefix(dir).ok().map(|path| path.display().to_string()))
+            .unwrap_or_else(|| path.display().to_string())
+    }
+
+    // The carets under the span on its first line. A span over several lines
+    // is underlined up to the end of its first line, and an empty span, the
+    // end of the source, gets a single caret.
+    fn underline(&self, text: &str, err: &LoxError) -> String {
+        let span = err.span();
+        let column = span.column.max(1);
+
+        // Tabs are kept, so that the carets line up with the source line.
+        let indent: String = text.chars()
+            .take(column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        let end_column = if span.end_line == span.line {
+            span.end_column
+        } else {
+            text.chars().count() + 1
+        };
+        let carets = "^".repeat(end_column.saturating_sub(column).max(1));
+
+        format!("{}{}", indent, self.paint(RED, &carets))
+    }
+}
@@ -110,6 +110,21 @@ impl Environment {
         Ok(environment)
     }
 
+    // Not in the book. The outermost scope of `env`. Each imported module has 
+    // its own, see ./src/lox_module.rs: a module's functions must look their 
+    // globals up in their module, not in the importing script.
+    pub fn global(env: &EnvironmentRef) -> EnvironmentRef {
+        let mut environment = Rc::clone(env);
+
+        loop {
+            let next = environment.borrow().enclosing.clone();
+            match next {
+                Some(enclosing) => environment = enclosing,
+                None => return environment,
+            }
+        }
+    }
+
     // This is only for variables the resolver already checked.
     // If it failed, it’d be a bug in the interpreter, not user code.
     // There are no runtime errors at this point — only logic errors.
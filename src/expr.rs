@@ -1,22 +1,25 @@
 /// Appendix II expr
 use std::rc::Rc;
 
-use super::token::{LiteralValue, Token};
+use super::token::{LiteralValue, Span, Token};
 use super::lox_runtime_error::LoxRuntimeError;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Assign {
     name: Token,
     value: Rc<Expr>,
+    span: Span,
 }
 
 impl Assign {
     pub fn new(name: Token, 
-        value: Rc<Expr>
+        value: Rc<Expr>, 
+        span: Span
     ) -> Self {
         Assign {
             name,
             value,
+            span,
         }
     }
 
@@ -28,6 +31,10 @@ impl Assign {
         &self.value
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -35,17 +42,20 @@ pub struct Binary {
     left: Rc<Expr>,
     operator: Token,
     right: Rc<Expr>,
+    span: Span,
 }
 
 impl Binary {
     pub fn new(left: Rc<Expr>, 
         operator: Token, 
-        right: Rc<Expr>
+        right: Rc<Expr>, 
+        span: Span
     ) -> Self {
         Binary {
             left,
             operator,
             right,
+            span,
         }
     }
 
@@ -61,6 +71,10 @@ impl Binary {
         &self.right
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -68,17 +82,20 @@ pub struct Call {
     callee: Rc<Expr>,
     paren: Token,
     arguments: Vec<Rc<Expr>>,
+    span: Span,
 }
 
 impl Call {
     pub fn new(callee: Rc<Expr>, 
         paren: Token, 
-        arguments: Vec<Rc<Expr>>
+        arguments: Vec<Rc<Expr>>, 
+        span: Span
     ) -> Self {
         Call {
             callee,
             paren,
             arguments,
+            span,
         }
     }
 
@@ -94,21 +111,28 @@ impl Call {
         &self.arguments
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Get {
     object: Rc<Expr>,
     name: Token,
+    span: Span,
 }
 
 impl Get {
     pub fn new(object: Rc<Expr>, 
-        name: Token
+        name: Token, 
+        span: Span
     ) -> Self {
         Get {
             object,
             name,
+            span,
         }
     }
 
@@ -120,17 +144,25 @@ impl Get {
         &self.name
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Grouping {
     expression: Rc<Expr>,
+    span: Span,
 }
 
 impl Grouping {
-    pub fn new(expression: Rc<Expr>) -> Self {
+    pub fn new(expression: Rc<Expr>, 
+        span: Span
+    ) -> Self {
         Grouping {
             expression,
+            span,
         }
     }
 
@@ -138,17 +170,138 @@ impl Grouping {
         &self.expression
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Index {
+    object: Rc<Expr>,
+    bracket: Token,
+    index: Rc<Expr>,
+    span: Span,
+}
+
+impl Index {
+    pub fn new(object: Rc<Expr>, 
+        bracket: Token, 
+        index: Rc<Expr>, 
+        span: Span
+    ) -> Self {
+        Index {
+            object,
+            bracket,
+            index,
+            span,
+        }
+    }
+
+    pub fn object(&self) -> &Rc<Expr> {
+        &self.object
+    }
+
+    pub fn bracket(&self) -> &Token {
+        &self.bracket
+    }
+
+    pub fn index(&self) -> &Rc<Expr> {
+        &self.index
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct IndexSet {
+    object: Rc<Expr>,
+    bracket: Token,
+    index: Rc<Expr>,
+    value: Rc<Expr>,
+    span: Span,
+}
+
+impl IndexSet {
+    pub fn new(object: Rc<Expr>, 
+        bracket: Token, 
+        index: Rc<Expr>, 
+        value: Rc<Expr>, 
+        span: Span
+    ) -> Self {
+        IndexSet {
+            object,
+            bracket,
+            index,
+            value,
+            span,
+        }
+    }
+
+    pub fn object(&self) -> &Rc<Expr> {
+        &self.object
+    }
+
+    pub fn bracket(&self) -> &Token {
+        &self.bracket
+    }
+
+    pub fn index(&self) -> &Rc<Expr> {
+        &self.index
+    }
+
+    pub fn value(&self) -> &Rc<Expr> {
+        &self.value
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct List {
+    elements: Vec<Rc<Expr>>,
+    span: Span,
+}
+
+impl List {
+    pub fn new(elements: Vec<Rc<Expr>>, 
+        span: Span
+    ) -> Self {
+        List {
+            elements,
+            span,
+        }
+    }
+
+    pub fn elements(&self) -> &Vec<Rc<Expr>> {
+        &self.elements
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Literal {
     value: LiteralValue,
+    span: Span,
 }
 
 impl Literal {
-    pub fn new(value: LiteralValue) -> Self {
+    pub fn new(value: LiteralValue, 
+        span: Span
+    ) -> Self {
         Literal {
             value,
+            span,
         }
     }
 
@@ -156,6 +309,10 @@ impl Literal {
         &self.value
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -163,17 +320,20 @@ pub struct Logical {
     left: Rc<Expr>,
     operator: Token,
     right: Rc<Expr>,
+    span: Span,
 }
 
 impl Logical {
     pub fn new(left: Rc<Expr>, 
         operator: Token, 
-        right: Rc<Expr>
+        right: Rc<Expr>, 
+        span: Span
     ) -> Self {
         Logical {
             left,
             operator,
             right,
+            span,
         }
     }
 
@@ -189,6 +349,50 @@ impl Logical {
         &self.right
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Map {
+    brace: Token,
+    keys: Vec<Rc<Expr>>,
+    values: Vec<Rc<Expr>>,
+    span: Span,
+}
+
+impl Map {
+    pub fn new(brace: Token, 
+        keys: Vec<Rc<Expr>>, 
+        values: Vec<Rc<Expr>>, 
+        span: Span
+    ) -> Self {
+        Map {
+            brace,
+            keys,
+            values,
+            span,
+        }
+    }
+
+    pub fn brace(&self) -> &Token {
+        &self.brace
+    }
+
+    pub fn keys(&self) -> &Vec<Rc<Expr>> {
+        &self.keys
+    }
+
+    pub fn values(&self) -> &Vec<Rc<Expr>> {
+        &self.values
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -196,17 +400,20 @@ pub struct Set {
     object: Rc<Expr>,
     name: Token,
     value: Rc<Expr>,
+    span: Span,
 }
 
 impl Set {
     pub fn new(object: Rc<Expr>, 
         name: Token, 
-        value: Rc<Expr>
+        value: Rc<Expr>, 
+        span: Span
     ) -> Self {
         Set {
             object,
             name,
             value,
+            span,
         }
     }
 
@@ -222,21 +429,28 @@ impl Set {
         &self.value
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Super {
     keyword: Token,
     method: Token,
+    span: Span,
 }
 
 impl Super {
     pub fn new(keyword: Token, 
-        method: Token
+        method: Token, 
+        span: Span
     ) -> Self {
         Super {
             keyword,
             method,
+            span,
         }
     }
 
@@ -248,17 +462,25 @@ impl Super {
         &self.method
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct This {
     keyword: Token,
+    span: Span,
 }
 
 impl This {
-    pub fn new(keyword: Token) -> Self {
+    pub fn new(keyword: Token, 
+        span: Span
+    ) -> Self {
         This {
             keyword,
+            span,
         }
     }
 
@@ -266,21 +488,28 @@ impl This {
         &self.keyword
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Unary {
     operator: Token,
     right: Rc<Expr>,
+    span: Span,
 }
 
 impl Unary {
     pub fn new(operator: Token, 
-        right: Rc<Expr>
+        right: Rc<Expr>, 
+        span: Span
     ) -> Self {
         Unary {
             operator,
             right,
+            span,
         }
     }
 
@@ -292,17 +521,25 @@ impl Unary {
         &self.right
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Variable {
     name: Token,
+    span: Span,
 }
 
 impl Variable {
-    pub fn new(name: Token) -> Self {
+    pub fn new(name: Token, 
+        span: Span
+    ) -> Self {
         Variable {
             name,
+            span,
         }
     }
 
@@ -310,6 +547,10 @@ impl Variable {
         &self.name
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 // Define enum
@@ -320,8 +561,12 @@ pub enum Expr {
     Call(Call),
     Get(Get),
     Grouping(Grouping),
+    Index(Index),
+    IndexSet(IndexSet),
+    List(List),
     Literal(Literal),
     Logical(Logical),
+    Map(Map),
     Set(Set),
     Super(Super),
     This(This),
@@ -336,8 +581,12 @@ pub trait Visitor<T> {
     fn visit_call_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
     fn visit_get_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
     fn visit_grouping_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
+    fn visit_index_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
+    fn visit_indexset_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
+    fn visit_list_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
     fn visit_literal_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
     fn visit_logical_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
+    fn visit_map_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
     fn visit_set_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
     fn visit_super_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
     fn visit_this_expr(&mut self, expr: Rc<Expr>) -> Result<T, LoxRuntimeError>;
@@ -354,8 +603,12 @@ impl Expr {
             Expr::Call(_) => visitor.visit_call_expr(expr),
             Expr::Get(_) => visitor.visit_get_expr(expr),
             Expr::Grouping(_) => visitor.visit_grouping_expr(expr),
+            Expr::Index(_) => visitor.visit_index_expr(expr),
+            Expr::IndexSet(_) => visitor.visit_indexset_expr(expr),
+            Expr::List(_) => visitor.visit_list_expr(expr),
             Expr::Literal(_) => visitor.visit_literal_expr(expr),
             Expr::Logical(_) => visitor.visit_logical_expr(expr),
+            Expr::Map(_) => visitor.visit_map_expr(expr),
             Expr::Set(_) => visitor.visit_set_expr(expr),
             Expr::Super(_) => visitor.visit_super_expr(expr),
             Expr::This(_) => visitor.visit_this_expr(expr),
@@ -363,5 +616,27 @@ impl Expr {
             Expr::Variable(_) => visitor.visit_variable_expr(expr),
         }
     }
+
+    #[allow(dead_code)]
+    pub fn span(&self) -> &Span {
+        match self {
+            Expr::Assign(node) => node.span(),
+            Expr::Binary(node) => node.span(),
+            Expr::Call(node) => node.span(),
+            Expr::Get(node) => node.span(),
+            Expr::Grouping(node) => node.span(),
+            Expr::Index(node) => node.span(),
+            Expr::IndexSet(node) => node.span(),
+            Expr::List(node) => node.span(),
+            Expr::Literal(node) => node.span(),
+            Expr::Logical(node) => node.span(),
+            Expr::Map(node) => node.span(),
+            Expr::Set(node) => node.span(),
+            Expr::Super(node) => node.span(),
+            Expr::This(node) => node.span(),
+            Expr::Unary(node) => node.span(),
+            Expr::Variable(node) => node.span(),
+        }
+    }
 }
 //< Appendix II expr
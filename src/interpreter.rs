@@ -9,11 +9,14 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use std::any::Any;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::fs::{canonicalize, read_to_string};
+use std::time::{Duration, Instant};
 
 use crate::token_type::TokenType;
 use super::lox_error::LoxError;
 use super::lox_runtime_error::LoxRuntimeError;
-use super::lox_error_helper::{error, sys_error, runtime_error}; 
+use super::lox_error_helper::{error, runtime_error, sys_error}; 
 use super::token::{LiteralValue, Token};
 use super::value::Value;
 use super::{expr, expr::Expr};
@@ -21,12 +24,35 @@ use super::{stmt, stmt::Stmt};
 use super::{unwrap_expr, unwrap_stmt};
 use super::environment::{Environment, EnvironmentRef};
 
-use super::lox_clock::LoxClock;
 use super::lox_function::LoxFunction;
 use super::lox_return::LoxReturn;
 
 use super::lox_class::{LoxClass, LoxFunctionsMap};
 use super::lox_instance::LoxInstance;
+use super::lox_list::LoxListMethod;
+use super::lox_map::{LoxMap, LoxMapMethod};
+use super::lox_exception::{caught_value, uncaught};
+use super::lox_module::LoxModule;
+use super::lox_native::{clock, Arity, NativeFunction};
+use super::lox_host;
+use super::scanner::Scanner;
+use super::parser::Parser;
+use super::resolver::Resolver;
+use super::stack_trace::{stack_trace, StackFrame};
+use super::cancel_token::CancelToken;
+
+/// Not in the book: how deeply calls may nest before a "Stack overflow." 
+/// runtime error. Every Lox call takes several Rust stack frames, over 10KB in
+/// debug builds: 64, as clox's FRAMES_MAX, fits a standard 2MB thread. A deeper
+/// limit needs a thread with a larger stack, see ./src/main.rs.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 64;
+
+/// Not in the book: how much Rust stack, in bytes, the calls in progress may 
+/// take before a "Stack overflow." runtime error. Expressions nested inside 
+/// each call add to it, which the call depth alone does not bound. 1MB leaves 
+/// room on a standard 2MB thread for the host's own frames and for the 
+/// nesting the parser allows, see `Parser::DEFAULT_MAX_NESTING`.
+pub const DEFAULT_MAX_STACK: usize = 1024 * 1024;
 
 // Remove generic from Interpreter to enable src/lox_function.rs' 
 // LoxFunction::call() to write the Interpreter::output.
@@ -69,19 +95,50 @@ impl<T: std::io::Write + Any> Writable for T {
 
 pub struct Interpreter {
     output: Box<dyn Writable>,
+    // Not in the book: runtime errors go here, rather than to `output`, which 
+    // is for `print` only. Standard error by default.
+    error_output: Box<dyn Writable>,
     // The outermost scope.
     globals: EnvironmentRef,
     // The current scope.
     environment: EnvironmentRef,
     // Pointer identity: raw pointer keys.
     locals: HashMap<*const Expr, usize>,
+    // Not in the book: imported modules, keyed by canonical path.
+    modules: HashMap<PathBuf, Rc<LoxModule>>,
+    // Not in the book: the script, then the modules being imported, as 
+    // canonical paths. Relative import paths resolve against the directory 
+    // of the last entry.
+    importing: Vec<PathBuf>,
+    // Not in the book: the calls in progress, outermost first, for stack 
+    // traces. See ./src/stack_trace.rs.
+    call_stack: Vec<StackFrame>,
+    // Not in the book: the limit of call_stack.
+    max_call_depth: usize,
+    // Not in the book: where the Rust stack was when the script, or the 
+    // outermost call from the host, started, and how much of it calls may take.
+    stack_base: Option<usize>,
+    max_stack: usize,
+    // Not in the book: the sandbox for untrusted scripts. The number of 
+    // statements and expressions executed so far, and their limit.
+    steps: u64,
+    step_budget: Option<u64>,
+    deadline: Option<Instant>,
+    cancel_token: Option<CancelToken>,
+    // Approximate bytes allocated so far by strings, fields, variables, lists 
+    // and maps, and their budget. Memory freed is not given back.
+    allocated: usize,
+    allocation_budget: Option<usize>,
+    // The line of the last statement or expression executed: the sandbox's 
+    // errors are not at a token.
+    line: usize,
 }
 
 impl Interpreter {
     fn initialize_globals(globals: &EnvironmentRef) {
         globals.borrow_mut().define(
             "clock".to_string(),
-            Value::LoxCallable(Rc::new(LoxClock)),
+            Value::LoxCallable(Rc::new(NativeFunction::new("clock", Arity::Fixed(0), clock))),
         );
     }
 
@@ -102,12 +159,80 @@ impl Interpreter {
         let boxed_output: Box<dyn Writable> = Box::new(output);
         Interpreter { 
             output: boxed_output,
+            error_output: Box::new(std::io::stderr()),
             environment: globals.clone(),
             globals: globals,
             locals: HashMap::new(),
+            modules: HashMap::new(),
+            importing: vec![],
+            call_stack: vec![StackFrame::script()],
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            stack_base: None,
+            max_stack: DEFAULT_MAX_STACK,
+            steps: 0,
+            step_budget: None,
+            deadline: None,
+            cancel_token: None,
+            allocated: 0,
+            allocation_budget: None,
+            line: 0,
         }
     }
 
+    /// Not in the book: where `report_error()` writes runtime errors, rather 
+    /// than standard error.
+    #[allow(dead_code)]
+    pub fn with_error_output<W: Writable + 'static>(mut self, error_output: W) -> Self {
+        self.error_output = Box::new(error_output);
+        self
+    }
+
+    /// Not in the book: stops the script with a "Step budget exceeded." error 
+    /// once it has executed `steps` statements and expressions in all. `catch` 
+    /// does not catch this error.
+    #[allow(dead_code)]
+    pub fn with_step_budget(mut self, steps: u64) -> Self {
+        self.steps = 0;
+        self.step_budget = Some(steps);
+        self
+    }
+
+    /// Not in the book: stops the script with a "Script timed out." error once 
+    /// `deadline` has passed. It is checked on every loop iteration and every 
+    /// call. `catch` does not catch this error.
+    #[allow(dead_code)]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Not in the book: stops the script with a "Script cancelled." error once 
+    /// `token` is cancelled, from any thread. It is checked on every loop 
+    /// iteration and every call. `catch` does not catch this error.
+    #[allow(dead_code)]
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel_token = Some(token);
+        self
+    }
+
+    /// Not in the book: stops the script with an "Allocation budget exceeded." 
+    /// error once it has allocated about `bytes` bytes in all, for new strings, 
+    /// instance fields, variables, list elements and map entries. Memory freed 
+    /// is not given back: this caps what the script allocates over its whole 
+    /// run, not what it holds at once. `catch` does not catch this error.
+    #[allow(dead_code)]
+    pub fn with_allocation_budget(mut self, bytes: usize) -> Self {
+        self.allocated = 0;
+        self.allocation_budget = Some(bytes);
+        self
+    }
+
+    /// The same as `with_deadline()`, `timeout` from now.
+    #[allow(dead_code)]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
     #[allow(dead_code)]
     // Used by tests.
     pub fn get_output(&self) -> &Box<dyn Writable> {
@@ -116,6 +241,12 @@ impl Interpreter {
 
     #[allow(dead_code)]
     // Used by tests.
+    pub fn get_error_output(&self) -> &dyn Writable {
+        self.error_output.as_ref()
+    }
+
+    #[allow(dead_code)]
+    // Used by tests. The error output is cleared too, when it is a byte stream.
     pub fn clear_output(&mut self) {
         use std::io::Cursor;
 
@@ -125,6 +256,11 @@ impl Interpreter {
         } else {
             panic!("Interpreter's output is not a mutable Cursor<Vec<u8>>");
         }
+
+        if let Some(cursor) = self.error_output.as_mut().as_any_mut().downcast_mut::<Cursor<Vec<u8>>>() {
+            cursor.get_mut().clear();
+            cursor.set_position(0);
+        }
     }
 
     #[allow(dead_code)]
@@ -141,22 +277,196 @@ impl Interpreter {
             Self::initialize_globals(&self.globals);
         }
         self.locals.clear();
+        self.modules.clear();
+        self.importing.clear();
         self.environment = Rc::clone(&self.globals);
         self.clear_output();
     }
 
+    /// Imports in the script at `path` resolve against its directory. Without 
+    /// a script path, they resolve against the current directory.
+    pub fn set_script_path(&mut self, path: &str) {
+        self.importing.clear();
+        if let Ok(path) = canonicalize(path) {
+            self.importing.push(path);
+        }
+    }
+
+    /// Defines, or redefines, a global variable.
+    #[allow(dead_code)]
+    pub fn define_global(&mut self, name: &str, value: Value) {
+        self.globals.borrow_mut().define(name.to_string(), value);
+    }
+
+    /// Defines a global native function, see ./src/lox_native.rs.
+    #[allow(dead_code)]
+    pub fn define_native(&mut self, name: &str, arity: Arity,
+        function: impl Fn(&mut Interpreter, &[Value]) -> Result<Value, LoxRuntimeError> + 'static) {
+        self.define_global(name, Value::LoxCallable(Rc::new(NativeFunction::new(name, arity, function))));
+    }
+
+    #[allow(dead_code)]
+    pub fn get_global(&self, name: &str) -> Option<Value> {
+        self.globals.borrow().values().get(name).cloned()
+    }
+
+    // Errors of natives and host objects are not at a token: they are reported 
+    // at the call, or at the property.
+    fn at_token(err: LoxRuntimeError, token: &Token) -> LoxRuntimeError {
+        match err {
+            LoxRuntimeError::Error(err) if err.line() == 0 => {
+                let at_token = error(token, err.err_msg()).with_trace(err.trace().to_vec());
+                LoxRuntimeError::Error(match err.source_path() {
+                    Some(path) => at_token.with_source_path(path),
+                    None => at_token,
+                })
+            }
+            err => err,
+        }
+    }
+
+    // Rust-specific: the `object.name` property, shared by `visit_get_expr()`
+    // and `call_method()`.
+    fn get_property(&self, object: Value, name: &Token) -> Result<Value, LoxRuntimeError> {
+        match object {
+            Value::LoxInstance(instance) => 
+                Ok(LoxInstance::get(Rc::clone(&instance), name)?),
+            Value::List(list) => 
+                Ok(LoxListMethod::get(Rc::clone(&list), name)?),
+            Value::Map(map) => 
+                Ok(LoxMapMethod::get(Rc::clone(&map), name)?),
+            Value::Module(module) => Ok(module.get(name)?),
+            Value::Host(host) => Ok(lox_host::get(&host, name)?),
+            _ => Err(runtime_error(name, "Only instances have properties."))
+        }
+    }
+
+    /// Not in the book: calls a function, a class, a bound method or a native 
+    /// from Rust, e.g. a callback which a script has defined. Errors are not 
+    /// at a token, they have no line. The sandbox's limits and the call depth 
+    /// are checked before the call, as for a call in the script.
+    #[allow(dead_code)]
+    pub fn call_value(&mut self, callee: &Value, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        // Not from a native the script has called: the host starts afresh.
+        if self.call_stack.len() == 1 {
+            self.stack_base = Some(stack_address());
+        }
+        self.check_limits()?;
+        self.check_stack()?;
+        match callee {
+            Value::LoxCallable(func) => {
+                if !func.is_variadic() && arguments.len() != func.arity() {
+                    return Err(sys_error("", &format!(
+                        "Expected {} arguments but got {}.", func.arity(), arguments.len())).into());
+                }
+                Rc::clone(func).call(self, arguments)
+            }
+            _ => Err(sys_error("", "Can only call functions and classes.").into())
+        }
+    }
+
+    /// Not in the book: calls the method `name` of `object`, an instance, a 
+    /// list, a map or a module, from Rust. See `call_value()`.
+    #[allow(dead_code)]
+    pub fn call_method(&mut self, object: &Value, name: &str, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        let name = Token::new(TokenType::Identifier, name.to_string(), None, 0);
+        let method = self.get_property(object.clone(), &name)?;
+        self.call_value(&method, arguments)
+    }
+
+    /// Calls nested deeper than `depth`, counting the script, are a "Stack 
+    /// overflow." runtime error.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.max_call_depth = depth;
+    }
+
+    /// Calls which take more than `bytes` of Rust stack in all are a "Stack 
+    /// overflow." runtime error. The thread running the script must have this 
+    /// much stack left, and some more.
+    pub fn set_max_stack(&mut self, bytes: usize) {
+        self.max_stack = bytes;
+    }
+
+    // Before a call: the calls in progress may not nest deeper than 
+    // `max_call_depth`, nor take more than `max_stack` of the Rust stack.
+    fn check_stack(&self) -> Result<(), LoxRuntimeError> {
+        let used = self.stack_base.map_or(0, |base| base.abs_diff(stack_address()));
+        if self.call_stack.len() >= self.max_call_depth || used > self.max_stack {
+            return Err(sys_error("", "Stack overflow.").into());
+        }
+        Ok(())
+    }
+
     fn write_output(&mut self, value: &str) {
         writeln!(self.output, "{}", value).expect("Failed to write output");
     }
 
-    fn evaluate(&mut self, expr: Rc<Expr>) -> Result<Value, LoxError> {
-        Ok(Expr::accept(expr, self)?)
+    /// Not in the book: writes `report`, such as an error returned by 
+    /// `interpret()` and rendered by the host, to the error output. 
+    /// `interpret()` itself writes nothing there.
+    pub fn report_error(&mut self, report: &str) {
+        write!(self.error_output, "{}", report).expect("Failed to write error output");
+    }
+
+    // Returns LoxRuntimeError rather than LoxError: a `throw` inside a called 
+    // function must unwind through the expression evaluating the call.
+    fn evaluate(&mut self, expr: Rc<Expr>) -> Result<Value, LoxRuntimeError> {
+        self.step(expr.span().line)?;
+        Expr::accept(expr, self)
     }
 
     fn execute(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        self.step(stmt.span().line)?;
         Stmt::accept(stmt, self)
     }
 
+    // Counts a statement or an expression against the step budget.
+    fn step(&mut self, line: usize) -> Result<(), LoxRuntimeError> {
+        self.line = line;
+        if let Some(budget) = self.step_budget {
+            self.steps += 1;
+            if self.steps > budget {
+                return Err(LoxRuntimeError::Abort(sys_error("", "Step budget exceeded.")));
+            }
+        }
+        Ok(())
+    }
+
+    // On loop iterations and calls.
+    fn check_limits(&self) -> Result<(), LoxRuntimeError> {
+        if self.cancel_token.as_ref().is_some_and(CancelToken::is_cancelled) {
+            return Err(LoxRuntimeError::Abort(sys_error("", "Script cancelled.")));
+        }
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline =>
+                Err(LoxRuntimeError::Abort(sys_error("", "Script timed out."))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Not in the book: counts `bytes` against the allocation budget.
+    pub fn allocate(&mut self, bytes: usize) -> Result<(), LoxRuntimeError> {
+        if let Some(budget) = self.allocation_budget {
+            self.allocated = self.allocated.saturating_add(bytes);
+            if self.allocated > budget {
+                return Err(LoxRuntimeError::Abort(sys_error("", "Allocation budget exceeded.")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Not in the book: counts a new variable or field called `name` against 
+    /// the allocation budget. Its value is counted where it is created.
+    pub fn allocate_entry(&mut self, name: &str) -> Result<(), LoxRuntimeError> {
+        self.allocate(name.len() + std::mem::size_of::<Value>())
+    }
+
+    /// Not in the book: counts `count` new list elements, or map keys and 
+    /// values, against the allocation budget.
+    pub fn allocate_values(&mut self, count: usize) -> Result<(), LoxRuntimeError> {
+        self.allocate(count.saturating_mul(std::mem::size_of::<Value>()))
+    }
+
     pub fn resolve(&mut self, expr: Rc<Expr>, depth: usize) {
         // Pointer identity, using pointer address: Rc::as_ptr(&expr).
         self.locals.insert(Rc::as_ptr(&expr), depth);
@@ -167,7 +477,7 @@ impl Interpreter {
         if let Some(&distance) = self.locals.get(&Rc::as_ptr(&expr)) {
             Ok(Environment::get_at(&self.environment, distance, name.lexeme()))
         } else {             
-            self.globals.borrow().get(name)
+            Environment::global(&self.environment).borrow().get(name)
         }
     }
 
@@ -187,6 +497,101 @@ impl Interpreter {
         result
     }
 
+    // Not in the book. Runs `f` in a new frame of the call stack. A runtime 
+    // error leaving the innermost frame gets the stack trace.
+    pub fn in_frame<T>(&mut self, frame: StackFrame, 
+        f: impl FnOnce(&mut Interpreter) -> Result<T, LoxRuntimeError>) -> Result<T, LoxRuntimeError> {
+        self.call_stack.push(frame);
+        let result = f(self).map_err(|err| self.with_stack_trace(err));
+        self.call_stack.pop();
+        result
+    }
+
+    fn with_stack_trace(&self, err: LoxRuntimeError) -> LoxRuntimeError {
+        match err {
+            LoxRuntimeError::Error(err) if err.trace().is_empty() => {
+                let trace = stack_trace(&self.call_stack, err.line());
+                LoxRuntimeError::Error(self.with_source_path(err.with_trace(trace)))
+            }
+            LoxRuntimeError::Abort(err) if err.trace().is_empty() => {
+                let trace = stack_trace(&self.call_stack, self.line);
+                LoxRuntimeError::Abort(self.with_source_path(err.with_trace(trace)))
+            }
+            err => err,
+        }
+    }
+
+    // Not in the book: the imported module the code running now is in, `None` 
+    // for the script. A native has no source: its errors are at its caller.
+    fn current_source(&self) -> Option<Rc<Path>> {
+        self.call_stack.iter()
+            .rev()
+            .find(|frame| !frame.is_native())
+            .and_then(|frame| frame.source().cloned())
+    }
+
+    fn with_source_path(&self, err: LoxError) -> LoxError {
+        match self.current_source() {
+            Some(path) => err.with_source_path(&path),
+            None => err,
+        }
+    }
+
+    fn import_module(&mut self, import: &stmt::Import) -> Result<Rc<LoxModule>, LoxRuntimeError> {
+        let base: PathBuf = self.importing.last()
+            .and_then(|script| script.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let path = canonicalize(base.join(import.path())).map_err(|_| runtime_error(
+            import.keyword(), &format!("Can't open module '{}'.", import.path())))?;
+
+        if let Some(module) = self.modules.get(&path) {
+            return Ok(Rc::clone(module));
+        }
+
+        if let Some(start) = self.importing.iter().position(|script| *script == path) {
+            let cycle: Vec<String> = self.importing[start..]
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|script| script.file_name().unwrap_or_default().to_string_lossy().to_string())
+                .collect();
+            return Err(runtime_error(import.keyword(), 
+                &format!("Import cycle: {}.", cycle.join(" -> "))));
+        }
+
+        let source = read_to_string(&path).map_err(|_| runtime_error(
+            import.keyword(), &format!("Can't open module '{}'.", import.path())))?;
+        let statements = self.load_module(&source).map_err(|err| runtime_error(
+            import.keyword(), &format!("Error in module '{}': {}", import.path(), err)))?;
+
+        // The module's own global scope: it can't see the importing script's globals.
+        let globals = Rc::new(RefCell::new(Environment::new()));
+        Self::initialize_globals(&globals);
+
+        // The importing frame is at the import, as it is at a call.
+        if let Some(frame) = self.call_stack.last_mut() {
+            frame.set_line(import.keyword().line());
+        }
+        self.importing.push(path.clone());
+        let frame = StackFrame::module(import.path()).with_source(Some(Rc::from(path.as_path())));
+        let result = self.in_frame(frame, 
+            |interpreter| interpreter.execute_block(&statements, Rc::clone(&globals)));
+        self.importing.pop();
+        result?;
+
+        let module = Rc::new(LoxModule::new(import.name().lexeme().to_string(), 
+            path.clone(), globals, statements));
+        self.modules.insert(path, Rc::clone(&module));
+        Ok(module)
+    }
+
+    fn load_module(&mut self, source: &str) -> Result<Vec<Rc<Stmt>>, LoxError> {
+        let tokens = Scanner::new(source).scan_tokens()?;
+        let statements = Parser::new(&tokens).parse()?;
+        Resolver::new(self).resolve(&statements)?;
+        Ok(statements)
+    }
+
     // Ruby rule: false and nil are falsey, and everything else is truthy. 
     fn is_truthy(&self, object: &Value) -> bool {
         match object {
@@ -196,6 +601,10 @@ impl Interpreter {
             Value::Nil => false,
             Value::LoxCallable(_) => true,
             Value::LoxInstance(_) => true,
+            Value::List(_) => true,
+            Value::Map(_) => true,
+            Value::Module(_) => true,
+            Value::Host(_) => true,
         }
     }
 
@@ -231,7 +640,13 @@ impl Interpreter {
 
     // Unlike the author original Java version, this version purposely 
     // keeps '.0' for f64.
-    fn stringify(&self, object: &Value) -> String {
+    pub(crate) fn stringify(&self, object: &Value) -> String {
+        self.stringify_nested(object, &mut Vec::new())
+    }
+
+    // Not in the book: `printing` has the lists and maps being printed, by 
+    // address. One which contains itself prints as `[...]` or `{...}` there.
+    fn stringify_nested(&self, object: &Value, printing: &mut Vec<*const ()>) -> String {
         match object {
             Value::Number(n) => format!("{:?}", n),
             Value::String(s) => s.to_string(),
@@ -239,6 +654,36 @@ impl Interpreter {
             Value::Nil => "nil".to_string(),
             Value::LoxCallable(callable) => callable.to_string(),
             Value::LoxInstance(instance) => format!("{}", instance.borrow().to_string()),
+            Value::List(list) => {
+                let address = Rc::as_ptr(list) as *const ();
+                if printing.contains(&address) {
+                    return "[...]".to_string();
+                }
+                printing.push(address);
+                let elements: Vec<String> = list.borrow()
+                    .iter()
+                    .map(|element| self.stringify_nested(element, printing))
+                    .collect();
+                printing.pop();
+                format!("[{}]", elements.join(", "))
+            }
+            Value::Map(map) => {
+                let address = Rc::as_ptr(map) as *const ();
+                if printing.contains(&address) {
+                    return "{...}".to_string();
+                }
+                printing.push(address);
+                let entries: Vec<String> = map.borrow()
+                    .entries()
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", self.stringify(key), 
+                        self.stringify_nested(value, printing)))
+                    .collect();
+                printing.pop();
+                format!("{{{}}}", entries.join(", "))
+            }
+            Value::Module(module) => module.to_string(),
+            Value::Host(host) => format!("{} instance", host.name()),
         }
     }
 
@@ -254,31 +699,57 @@ impl Interpreter {
         Ok(self.stringify(&value))
     }
 
+    /// Not in the book, used by [`crate::Lox::eval()`]. Executes `statements` 
+    /// up to the first runtime error, which is returned rather than written to 
+    /// the output. The value is that of the last statement if it is an 
+    /// expression statement, `nil` otherwise.
+    #[allow(dead_code)]
+    pub fn interpret_value(&mut self, statements: &[Rc<Stmt>]) -> Result<Value, LoxError> {
+        let mut value = Value::Nil;
+        self.stack_base = Some(stack_address());
+
+        for statement in statements {
+            let result = match statement.as_ref() {
+                Stmt::Expression(stmt) => self.evaluate(Rc::clone(stmt.expression())),
+                _ => self.execute(Rc::clone(statement)).map(|_| Value::Nil),
+            };
+            value = result.map_err(|err| LoxError::from(self.with_stack_trace(err)))?;
+        }
+
+        Ok(value)
+    }
+
     // Interpret all statements, captures all errors.
     // 
-    // Both evaluation ( Interpreter's successful evaluation output ) results, 
-    // and evaluation errors are written to `output` in occurrence-order.
+    // Evaluation ( Interpreter's successful evaluation output ) results are 
+    // written to `output`.
     // 
-    // Evaluation errors are also captured in occurrence-order and returned 
-    // via Err(LoxError). When there are multiple errors, they are separated 
-    // by a newline ( \n ) character.
+    // Evaluation errors are captured in occurrence-order and returned via 
+    // Err(LoxError), not written anywhere: the host reports them, through the 
+    // error sink with `report_error()`. An error of the sandbox's limits stops 
+    // the remaining statements.
     pub fn interpret(&mut self, statements: &Vec<Rc<Stmt>>) -> Result<(), LoxError> {
-        let mut err_msgs: Vec<String> = vec![];
+        let mut errors: Vec<LoxError> = vec![];
+        self.stack_base = Some(stack_address());
 
         for statement in statements {
             match self.execute(Rc::clone(statement)) {
                 Ok(_) => {},
                 Err(err) => {
-                    err_msgs.push(format!("{}", err));
-                    self.write_output(&format!("{}", err));
+                    // The sandbox's limits stop the whole script.
+                    let abort = matches!(err, LoxRuntimeError::Abort(_));
+                    errors.push(self.with_stack_trace(err).into());
+                    if abort {
+                        break;
+                    }
                 }
             }
         }
 
-        if err_msgs.len() == 0 {
+        if errors.is_empty() {
             Ok(())
         } else {
-            Err(sys_error("", &err_msgs.join("\n")))
+            Err(LoxError::from_errors(errors))
         }
     }
 }
@@ -294,7 +765,7 @@ impl expr::Visitor<Value> for Interpreter {
         if let Some(&distance) = self.locals.get(&Rc::as_ptr(&expr)) {
             Environment::assign_at(&self.environment, distance, assign.name(), result)?;
         } else {
-            self.globals.borrow_mut().assign(assign.name(), result)?;
+            Environment::global(&self.environment).borrow_mut().assign(assign.name(), result)?;
         }
 
         Ok(value)
@@ -338,6 +809,7 @@ impl expr::Visitor<Value> for Interpreter {
                         Ok(Value::Number(ln + rn))
                     }
                     (Value::String(ls), Value::String(rs)) => {
+                        self.allocate(ls.len() + rs.len())?;
                         Ok(Value::String(format!("{}{}", ls, rs)))
                     }                    
                     _ => Err(runtime_error(operator, 
@@ -363,11 +835,17 @@ impl expr::Visitor<Value> for Interpreter {
 
         match callee {
             Value::LoxCallable(func) => {
-                if arguments.len() != func.arity() {
+                if !func.is_variadic() && arguments.len() != func.arity() {
                     return Err(runtime_error(call.paren(), &format!(
                         "Expected {} arguments but got {}.", func.arity(), arguments.len())));
                 }
-                Ok(func.call(self, arguments)?)
+                self.check_limits()?;
+                self.check_stack().map_err(|err| Self::at_token(err, call.paren()))?;
+                // The line this frame is at, in stack traces.
+                if let Some(frame) = self.call_stack.last_mut() {
+                    frame.set_line(call.paren().line());
+                }
+                func.call(self, arguments).map_err(|err| Self::at_token(err, call.paren()))
             }
             _ => Err(runtime_error(call.paren(), "Can only call functions and classes."))
         }        
@@ -377,16 +855,73 @@ impl expr::Visitor<Value> for Interpreter {
         let get = unwrap_expr!(expr, Get);
         let object = self.evaluate(Rc::clone(get.object()))?;
 
-        match object {
-            Value::LoxInstance(instance) => 
-                Ok(LoxInstance::get(Rc::clone(&instance), get.name())?),
-            _ => Err(runtime_error(get.name(), "Only instances have properties."))
-        }
+        self.get_property(object, get.name())
     }
 
     fn visit_grouping_expr(&mut self, expr: Rc<Expr>) -> Result<Value, LoxRuntimeError> {
         let grouping = unwrap_expr!(expr, Grouping);
-        Ok(self.evaluate(Rc::clone(grouping.expression()))?)
+        self.evaluate(Rc::clone(grouping.expression()))
+    }
+
+    fn visit_index_expr(&mut self, expr: Rc<Expr>) -> Result<Value, LoxRuntimeError> {
+        let index = unwrap_expr!(expr, Index);
+
+        let object = self.evaluate(Rc::clone(index.object()))?;
+        let position = self.evaluate(Rc::clone(index.index()))?;
+
+        match object {
+            Value::List(list) => {
+                let list = list.borrow();
+                let i = LoxListMethod::to_index(index.bracket(), &position, list.len())?;
+                Ok(list[i].clone())
+            }
+            Value::Map(map) => {
+                LoxMap::check_key(index.bracket(), &position)?;
+                map.borrow().get(&position).ok_or_else(|| runtime_error(index.bracket(), 
+                    &format!("Undefined key '{}'.", self.stringify(&position))))
+            }
+            _ => Err(runtime_error(index.bracket(), "Only lists and maps can be indexed."))
+        }
+    }
+
+    fn visit_indexset_expr(&mut self, expr: Rc<Expr>) -> Result<Value, LoxRuntimeError> {
+        let index_set = unwrap_expr!(expr, IndexSet);
+
+        let object = self.evaluate(Rc::clone(index_set.object()))?;
+        let position = self.evaluate(Rc::clone(index_set.index()))?;
+
+        match object {
+            Value::List(list) => {
+                let value = self.evaluate(Rc::clone(index_set.value()))?;
+                let mut list = list.borrow_mut();
+                let i = LoxListMethod::to_index(index_set.bracket(), &position, list.len())?;
+                list[i] = value.clone();
+                Ok(value)
+            }
+            Value::Map(map) => {
+                LoxMap::check_key(index_set.bracket(), &position)?;
+                let value = self.evaluate(Rc::clone(index_set.value()))?;
+                // A new key and its value. Replacing a value allocates nothing.
+                if !map.borrow().contains_key(&position) {
+                    self.allocate_values(2)?;
+                }
+                map.borrow_mut().insert(position, value.clone());
+                Ok(value)
+            }
+            _ => Err(runtime_error(index_set.bracket(), "Only lists and maps can be indexed."))
+        }
+    }
+
+    fn visit_list_expr(&mut self, expr: Rc<Expr>) -> Result<Value, LoxRuntimeError> {
+        let list = unwrap_expr!(expr, List);
+
+        let elements: Vec<Value> = list.elements()
+            .iter()
+            .map(|element| self.evaluate(Rc::clone(element)))
+            .collect::<Result<_, _>>()?;
+
+        self.allocate_values(elements.len())?;
+        Ok(Value::List(Rc::new(RefCell::new(elements))))
     }
 
     fn visit_literal_expr(&mut self, expr: Rc<Expr>) -> Result<Value, LoxRuntimeError> {
@@ -412,6 +947,21 @@ impl expr::Visitor<Value> for Interpreter {
         }
     }
 
+    fn visit_map_expr(&mut self, expr: Rc<Expr>) -> Result<Value, LoxRuntimeError> {
+        let inner = unwrap_expr!(expr, Map);
+
+        let mut map = LoxMap::new();
+        for (key, value) in inner.keys().iter().zip(inner.values()) {
+            let key = self.evaluate(Rc::clone(key))?;
+            LoxMap::check_key(inner.brace(), &key)?;
+            let value = self.evaluate(Rc::clone(value))?;
+            map.insert(key, value);
+        }
+
+        self.allocate_values(2 * map.len())?;
+        Ok(Value::Map(Rc::new(RefCell::new(map))))
+    }
+
     fn visit_set_expr(&mut self, expr: Rc<Expr>) -> Result<Value, LoxRuntimeError> {
         let set = unwrap_expr!(expr, Set);
 
@@ -420,9 +970,19 @@ impl expr::Visitor<Value> for Interpreter {
         match object {
             Value::LoxInstance(inst) => {
                 let value = self.evaluate(Rc::clone(set.value()))?;
+                // Assigning an existing field allocates nothing.
+                if inst.borrow().get_field(set.name().lexeme()).is_none() {
+                    self.allocate_entry(set.name().lexeme())?;
+                }
                 inst.borrow_mut().set(set.name(), value.clone());
                 Ok(value)
             },
+            Value::Host(host) => {
+                let value = self.evaluate(Rc::clone(set.value()))?;
+                host.set(set.name().lexeme(), value.clone())
+                    .map_err(|err| Self::at_token(err, set.name()))?;
+                Ok(value)
+            },
             _ => Err(runtime_error(set.name(), "Only instances have fields."))
         }
     }
@@ -501,6 +1061,10 @@ impl stmt::Visitor<()> for Interpreter {
         Ok(())
     }
 
+    fn visit_break_stmt(&mut self, _: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        Err(LoxRuntimeError::Break)
+    }
+
     fn visit_class_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let class = unwrap_stmt!(stmt, Class);
 
@@ -533,7 +1097,8 @@ impl stmt::Visitor<()> for Interpreter {
         let mut methods: LoxFunctionsMap = HashMap::new();
         for method in class.methods() {
             let function: LoxFunction = LoxFunction::new(method.as_ref().clone(), 
-                self.environment.clone(), method.name().lexeme() == "init");
+                self.environment.clone(), method.name().lexeme() == "init")
+                .with_source(self.current_source());
             methods.insert(method.name().lexeme().to_string(), function);
         }
 
@@ -551,6 +1116,10 @@ impl stmt::Visitor<()> for Interpreter {
         Ok(())
     }
 
+    fn visit_continue_stmt(&mut self, _: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        Err(LoxRuntimeError::Continue)
+    }
+
     fn visit_expression_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let expression = unwrap_stmt!(stmt, Expression);
         self.evaluate(Rc::clone(expression.expression()))?;
@@ -561,7 +1130,8 @@ impl stmt::Visitor<()> for Interpreter {
     fn visit_function_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let inner = unwrap_stmt!(stmt, Function);
 
-        let function: LoxFunction = LoxFunction::new(inner.clone(), Rc::clone(&self.environment), false);
+        let function: LoxFunction = LoxFunction::new(inner.clone(), Rc::clone(&self.environment), false)
+            .with_source(self.current_source());
         self.environment.borrow_mut().define(
             inner.name().lexeme().to_string(), 
             Value::LoxCallable(Rc::new(function))
@@ -582,6 +1152,17 @@ impl stmt::Visitor<()> for Interpreter {
         }
     }
 
+    fn visit_import_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let import = unwrap_stmt!(stmt, Import);
+
+        let module = self.import_module(import)?;
+        self.environment.borrow_mut().define(
+            import.name().lexeme().to_string(), 
+            Value::Module(module)
+        );
+        Ok(())
+    }
+
     fn visit_print_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let print = unwrap_stmt!(stmt, Print);
 
@@ -607,6 +1188,49 @@ impl stmt::Visitor<()> for Interpreter {
         Err(LoxRuntimeError::Return(LoxReturn { value }))
     }
 
+    fn visit_throw_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Throw);
+
+        let value: Value = self.evaluate(Rc::clone(inner.value()))?;
+        let keyword = inner.keyword();
+        let trace = stack_trace(&self.call_stack, keyword.line());
+        let err = self.with_source_path(uncaught(&value, keyword).with_trace(trace));
+        Err(LoxRuntimeError::Throw(value, Box::new(err)))
+    }
+
+    fn visit_try_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Try);
+
+        let mut result = self.execute_block(inner.body(), Rc::new(RefCell::new(
+            Environment::new_local_scope(Rc::clone(&self.environment))
+        )));
+
+        if let (Some(name), Some(catch_body)) = (inner.catch_name(), inner.catch_body())
+            && let Err(err) = result {
+            result = match caught_value(err) {
+                Ok(value) => {
+                    let environment = Rc::new(RefCell::new(
+                        Environment::new_local_scope(Rc::clone(&self.environment))
+                    ));
+                    environment.borrow_mut().define(name.lexeme().to_string(), value);
+                    self.execute_block(catch_body, environment)
+                }
+                // `return`, `break` and `continue` are not exceptions.
+                Err(err) => Err(err),
+            };
+        }
+
+        // `finally` runs however the try and catch blocks completed. If it 
+        // completes normally, their result carries on unwinding.
+        if let Some(finally_body) = inner.finally_body() {
+            self.execute_block(finally_body, Rc::new(RefCell::new(
+                Environment::new_local_scope(Rc::clone(&self.environment))
+            )))?;
+        }
+
+        result
+    }
+
     fn visit_var_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let var = unwrap_stmt!(stmt, Var);
         let value = if let Some(initializer) = var.initializer() {
@@ -615,6 +1239,7 @@ impl stmt::Visitor<()> for Interpreter {
             Value::Nil
         };
 
+        self.allocate_entry(var.name().lexeme())?;
         self.environment.borrow_mut().define(var.name().lexeme().to_string(), value);
         Ok(())
     }
@@ -624,9 +1249,30 @@ impl stmt::Visitor<()> for Interpreter {
 
         let mut value: Value = self.evaluate(Rc::clone(inner.condition()))?;
         while self.is_truthy(&value) {
-            self.execute(Rc::clone(inner.body()))?;
+            match self.execute(Rc::clone(inner.body())) {
+                Ok(_) | Err(LoxRuntimeError::Continue) => {},
+                Err(LoxRuntimeError::Break) => break,
+                Err(err) => return Err(err),
+            }
+
+            // Desugared `for` loop: the increment clause runs even when the 
+            // body was cut short by `continue`.
+            if let Some(increment) = inner.increment() {
+                self.evaluate(Rc::clone(increment))?;
+            }
+
+            self.check_limits()?;
             value = self.evaluate(Rc::clone(inner.condition()))?;
         }
         Ok(())
     }
 }
+
+// Not in the book: the address of a local variable, which tells how deep the 
+// Rust stack is. Stacks grow down on most platforms, but only the distance 
+// between two addresses is used.
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker as *const u8) as usize
+}
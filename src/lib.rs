@@ -10,7 +10,6 @@ pub mod token_type;
 pub mod token;
 pub mod lox_callable;
 pub mod value;
-pub mod lox_clock;
 pub mod scanner;
 pub mod expr;
 pub mod stmt;
@@ -24,6 +23,19 @@ pub mod lox_runtime_error;
 pub mod resolver;
 pub mod lox_class;
 pub mod lox_instance;
+pub mod lox_list;
+pub mod lox_map;
+pub mod lox_exception;
+pub mod lox_module;
+pub mod lox_native;
+pub mod lox_host;
+pub mod cancel_token;
+pub mod vm;
+pub mod diagnostics;
+pub mod stack_trace;
+pub mod lox;
+
+pub use lox::Lox;
 
 #[macro_export]
 macro_rules! unwrap_expr {
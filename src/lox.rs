@@ -0,0 +1,166 @@
+/* Date Created: 17/10/2026. */
+
+//! Rust-specific. The API for Rust programs which embed Lox: what ./src/main.rs
+//! does by hand, scanning, parsing, resolving and interpreting, in one call.
+//! State persists between calls:
+//!
+//! ```
+//! use rlox::Lox;
+//! use rlox::value::Value;
+//!
+//! let mut lox = Lox::with_output(std::io::sink());
+//! lox.set_global("base", Value::Number(40.0));
+//! lox.eval("var answer = base + 2;").unwrap();
+//! assert_eq!(lox.eval("answer;").unwrap(), Value::Number(42.0));
+//! ```
+//!
+//! Errors are returned, not printed, and tagged with the [`Phase`] which
+//! reports them.
+
+use std::rc::Rc;
+use std::fs::read_to_string;
+use std::time::{Duration, Instant};
+
+use super::lox_error::{LoxError, Phase};
+use super::value::Value;
+use super::stmt::Stmt;
+use super::scanner::Scanner;
+use super::parser::{Parser, DEFAULT_MAX_NESTING};
+use super::resolver::Resolver;
+use super::interpreter::{Interpreter, Writable};
+use super::lox_native::Arity;
+use super::lox_runtime_error::LoxRuntimeError;
+use super::cancel_token::CancelToken;
+
+pub struct Lox {
+    interpreter: Interpreter,
+    max_nesting: usize,
+    // Interpreter::locals is keyed by the addresses of resolved expressions:
+    // the statements of every call are kept, so that no address is reused.
+    statements: Vec<Rc<Stmt>>,
+}
+
+impl Default for Lox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Lox {
+    /// `print` writes to stdout.
+    pub fn new() -> Self {
+        Self::with_output(std::io::stdout())
+    }
+
+    pub fn with_output<W: Writable + 'static>(output: W) -> Self {
+        Lox {
+            interpreter: Interpreter::new(output),
+            max_nesting: DEFAULT_MAX_NESTING,
+            statements: vec![],
+        }
+    }
+
+    /// See `Interpreter::with_step_budget()`.
+    pub fn with_step_budget(mut self, steps: u64) -> Self {
+        self.interpreter = self.interpreter.with_step_budget(steps);
+        self
+    }
+
+    /// See `Interpreter::with_deadline()`.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.interpreter = self.interpreter.with_deadline(deadline);
+        self
+    }
+
+    /// See `Interpreter::with_cancel_token()`.
+    pub fn with_cancel_token(mut self, token: CancelToken) -> Self {
+        self.interpreter = self.interpreter.with_cancel_token(token);
+        self
+    }
+
+    /// See `Interpreter::with_allocation_budget()`.
+    pub fn with_allocation_budget(mut self, bytes: usize) -> Self {
+        self.interpreter = self.interpreter.with_allocation_budget(bytes);
+        self
+    }
+
+    /// See `Interpreter::with_timeout()`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.interpreter = self.interpreter.with_timeout(timeout);
+        self
+    }
+
+    /// For everything else the tree-walker offers, e.g. its output.
+    pub fn interpreter(&self) -> &Interpreter {
+        &self.interpreter
+    }
+
+    /// See `Interpreter::set_max_call_depth()`.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.interpreter.set_max_call_depth(depth);
+    }
+
+    /// See `Interpreter::set_max_stack()`.
+    pub fn set_max_stack(&mut self, bytes: usize) {
+        self.interpreter.set_max_stack(bytes);
+    }
+
+    /// See `Parser::set_max_nesting()`.
+    pub fn set_max_nesting(&mut self, max_nesting: usize) {
+        self.max_nesting = max_nesting;
+    }
+
+    pub fn set_global(&mut self, name: &str, value: Value) {
+        self.interpreter.define_global(name, value);
+    }
+
+    /// See `Interpreter::define_native()`.
+    pub fn define_native(&mut self, name: &str, arity: Arity,
+        function: impl Fn(&mut Interpreter, &[Value]) -> Result<Value, LoxRuntimeError> + 'static) {
+        self.interpreter.define_native(name, arity, function);
+    }
+
+    pub fn get_global(&self, name: &str) -> Option<Value> {
+        self.interpreter.get_global(name)
+    }
+
+    /// Runs `source` to the first runtime error. The value is that of the last
+    /// statement if it is an expression statement, `nil` otherwise.
+    pub fn eval(&mut self, source: &str) -> Result<Value, LoxError> {
+        let tokens = Scanner::new(source).scan_tokens()
+            .map_err(|err| err.with_phase(Phase::Scan))?;
+
+        let mut parser = Parser::new(&tokens);
+        parser.set_max_nesting(self.max_nesting);
+        let statements = parser.parse()
+            .map_err(|err| err.with_phase(Phase::Parse))?;
+
+        Resolver::new(&mut self.interpreter).resolve(&statements)
+            .map_err(|err| LoxError::from(err).with_phase(Phase::Resolve))?;
+        self.statements.extend(statements.iter().cloned());
+
+        self.interpreter.interpret_value(&statements)
+            .map_err(|err| err.with_phase(Phase::Runtime))
+    }
+
+    /// See `Interpreter::call_value()`.
+    pub fn call_value(&mut self, callee: &Value, arguments: Vec<Value>) -> Result<Value, LoxError> {
+        self.interpreter.call_value(callee, arguments)
+            .map_err(|err| LoxError::from(err).with_phase(Phase::Runtime))
+    }
+
+    /// See `Interpreter::call_method()`.
+    pub fn call_method(&mut self, object: &Value, name: &str, arguments: Vec<Value>) -> Result<Value, LoxError> {
+        self.interpreter.call_method(object, name, arguments)
+            .map_err(|err| LoxError::from(err).with_phase(Phase::Runtime))
+    }
+
+    /// The same as `eval()`, imports resolve against the directory of `path`.
+    pub fn run_file(&mut self, path: &str) -> Result<Value, LoxError> {
+        let source = read_to_string(path).map_err(|err| LoxError::new(0, "",
+            &format!("Can't read '{}': {}.", path, err)).with_phase(Phase::Io))?;
+
+        self.interpreter.set_script_path(path);
+        self.eval(&source)
+    }
+}
@@ -14,6 +14,11 @@ use super::value::Value;
 
 pub trait LoxCallable: fmt::Debug + fmt::Display {
     fn arity(&self) -> usize;
+    // Not in the book: a variadic callable takes any number of arguments, 
+    // see ./src/lox_native.rs.
+    fn is_variadic(&self) -> bool {
+        false
+    }
     fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError>;
     #[allow(dead_code)]
     // No downcast_ref::<T>() anywhere in the code yet.
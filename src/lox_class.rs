@@ -68,9 +68,10 @@ impl LoxCallable for LoxClass {
         let instance_ref = Rc::new(RefCell::new(instance));
 
         if let Some(initializer) = self.find_method("init") {
-            initializer.bind(
-                Rc::clone(&instance_ref))
-                .call(interpreter, arguments)?;
+            // The frame is the initializer's, the same as clox.
+            interpreter.in_frame(initializer.frame(), 
+                |interpreter| initializer.bind(Rc::clone(&instance_ref))
+                    .call_body(interpreter, arguments))?;
         }
         Ok(Value::LoxInstance(instance_ref))
     }
@@ -6,6 +6,15 @@
 */
 
 //! Error reporting, includes the line number and the actual error message.
+//! 
+//! Errors reported at a token also carry the token's [`Span`]: its columns and 
+//! byte offsets in the source. An error can also carry a help note, and the 
+//! scanner, the parser, the resolver and the interpreter collect all their 
+//! errors into a single one, see [`LoxError::from_errors()`]. Runtime errors 
+//! carry a stack trace, and the path of the module they are in. [`crate::Lox`] 
+//! also tags errors with the [`Phase`] which reports them.
+//! 
+//! ./src/diagnostics.rs renders them with source snippets.
 
 // To run test for this module only: 
 // 
@@ -14,19 +23,88 @@
 //     * cargo test lox_error::tests::test_valid_error_message -- --exact [--nocapture]
 //     * cargo test lox_error::tests::test_valid_no_line -- --exact [--nocapture]
 //     * cargo test lox_error::tests::test_valid_no_line_no_lexeme -- --exact [--nocapture]
+//     * cargo test lox_error::tests::test_span -- --exact [--nocapture]
+//     * cargo test lox_error::tests::test_from_errors -- --exact [--nocapture]
+//     * cargo test lox_error::tests::test_phase -- --exact [--nocapture]
 //
 
 use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::token::Span;
+
+/// Where an error comes from.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    // Reading the script.
+    Io,
+    Scan,
+    Parse,
+    Resolve,
+    Runtime,
+}
 
 pub struct LoxError {
     line: usize,
     lexeme: String,
-    err_msg: String
+    err_msg: String,
+    span: Span,
+    // Boxed: most errors have neither, and `Result<T, LoxError>` stays small.
+    notes: Option<Box<Notes>>,
+}
+
+#[derive(Debug, Default)]
+struct Notes {
+    help: Option<String>,
+    // The individual errors when this error collects several.
+    errors: Vec<LoxError>,
+    // Runtime errors only, see ./src/stack_trace.rs.
+    trace: Vec<String>,
+    // Runtime errors only: the imported module the error is in.
+    source_path: Option<PathBuf>,
+    phase: Option<Phase>,
 }
 
 impl LoxError {
     pub fn new(line: usize, lexeme: &str, msg: &str) -> LoxError {
-        LoxError{line, lexeme: lexeme.to_string(), err_msg: msg.to_string()}
+        LoxError{line, lexeme: lexeme.to_string(), err_msg: msg.to_string(), 
+            span: Span::default(), notes: None}
+    }
+
+    /// Collects all errors of a phase. The message is the individual errors 
+    /// separated by a newline ( \n ) character.
+    pub fn from_errors(errors: Vec<LoxError>) -> LoxError {
+        let err_msgs: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+        let mut err = LoxError::new(0, "", &err_msgs.join("\n"));
+        err.notes.get_or_insert_default().errors = errors;
+        err
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.notes.get_or_insert_default().help = Some(help.to_string());
+        self
+    }
+
+    pub fn with_trace(mut self, trace: Vec<String>) -> Self {
+        self.notes.get_or_insert_default().trace = trace;
+        self
+    }
+
+    pub fn with_source_path(mut self, path: &Path) -> Self {
+        self.notes.get_or_insert_default().source_path = Some(path.to_path_buf());
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_phase(mut self, phase: Phase) -> Self {
+        self.notes.get_or_insert_default().phase = Some(phase);
+        self
     }
 
     #[allow(dead_code)]
@@ -43,6 +121,45 @@ impl LoxError {
     pub fn err_msg(&self) -> &str {
         &self.err_msg
     }
+
+    /// A default, all zeros span when the error is not at a token.
+    #[allow(dead_code)]
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    #[allow(dead_code)]
+    pub fn help(&self) -> Option<&str> {
+        self.notes.as_ref().and_then(|notes| notes.help.as_deref())
+    }
+
+    /// The stack trace, innermost frame first. Empty for all but runtime errors.
+    #[allow(dead_code)]
+    pub fn trace(&self) -> &[String] {
+        self.notes.as_ref().map_or(&[], |notes| notes.trace.as_slice())
+    }
+
+    /// The module a runtime error is in. `None` when it is in the script being 
+    /// run, and for all other errors: their line is in that script's source.
+    #[allow(dead_code)]
+    pub fn source_path(&self) -> Option<&Path> {
+        self.notes.as_ref().and_then(|notes| notes.source_path.as_deref())
+    }
+
+    /// `None` unless set with `with_phase()`.
+    #[allow(dead_code)]
+    pub fn phase(&self) -> Option<Phase> {
+        self.notes.as_ref().and_then(|notes| notes.phase)
+    }
+
+    /// The individual errors, or just this one when it does not collect several.
+    #[allow(dead_code)]
+    pub fn errors(&self) -> Vec<&LoxError> {
+        match &self.notes {
+            Some(notes) if !notes.errors.is_empty() => notes.errors.iter().collect(),
+            _ => vec![self],
+        }
+    }
 }
 
 impl fmt::Debug for LoxError {
@@ -51,6 +168,8 @@ impl fmt::Debug for LoxError {
             .field("line", &self.line) 
             .field("lexeme", &self.lexeme)
             .field("err_msg", &self.err_msg)
+            .field("span", &self.span)
+            .field("notes", &self.notes)
             .finish()
     }
 }
@@ -94,4 +213,46 @@ mod tests {
         let err = LoxError::new(0, "", "this is a test error");
         assert_eq!("this is a test error", err.to_string());
     }
+
+    #[test]
+    fn test_span() {
+        let span = Span { line: 2, column: 5, end_line: 2, end_column: 8, start: 12, end: 15 };
+        let err = LoxError::new(2, "idx", "this is a test error").with_span(span);
+        assert_eq!(&span, err.span());
+        // Display is unchanged.
+        assert_eq!("[line 2] Error at 'idx': this is a test error", err.to_string());
+
+        let err = LoxError::new(0, "", "this is a test error");
+        assert_eq!(&Span::default(), err.span());
+    }
+
+    #[test]
+    fn test_from_errors() {
+        let err = LoxError::from_errors(vec![
+            LoxError::new(1, "a", "first error"),
+            LoxError::new(2, "b", "second error").with_help("a help note"),
+        ]);
+        assert_eq!("[line 1] Error at 'a': first error\n[line 2] Error at 'b': second error", 
+            err.to_string());
+
+        let errors = err.errors();
+        assert_eq!(2, errors.len());
+        assert_eq!("first error", errors[0].err_msg());
+        assert_eq!(None, errors[0].help());
+        assert_eq!(Some("a help note"), errors[1].help());
+
+        let err = LoxError::new(3, "c", "single error");
+        assert_eq!("single error", err.errors()[0].err_msg());
+    }
+
+    #[test]
+    fn test_phase() {
+        let err = LoxError::new(1, "a", "this is a test error");
+        assert_eq!(None, err.phase());
+
+        let err = err.with_phase(Phase::Parse);
+        assert_eq!(Some(Phase::Parse), err.phase());
+        // Display is unchanged.
+        assert_eq!("[line 1] Error at 'a': this is a test error", err.to_string());
+    }
 }
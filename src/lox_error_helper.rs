@@ -15,7 +15,7 @@ pub fn scanner_error(line: usize, c: char, message: &str) -> LoxError {
 /// Global error report function. All code using Token should 
 /// call this to create return error for `Result<T, LoxError>`.
 pub fn error(token: &Token, message: &str) -> LoxError {
-    LoxError::new(token.line(), token.lexeme(), message)
+    LoxError::new(token.line(), token.lexeme(), message).with_span(*token.span())
 }
 
 /// Global error report function. All code which do not use Token 
@@ -0,0 +1,60 @@
+/* Date Created: 17/10/2026. */
+
+//! Not in the book. Support for `throw` and `try`/`catch`/`finally`.
+//!
+//! A `throw` unwinds as [`LoxRuntimeError::Throw`] until a `try` statement 
+//! catches it. Native runtime errors, such as `Operand must be a number.`, 
+//! unwind as [`LoxRuntimeError::Error`]: `catch` converts them into an `Error` 
+//! instance with a `message` and a `line` field, so that scripts handle both 
+//! the same way.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::lox_error::LoxError;
+use super::lox_error_helper::{error, sys_error};
+use super::lox_runtime_error::LoxRuntimeError;
+use super::lox_class::LoxClass;
+use super::lox_instance::LoxInstance;
+use super::value::Value;
+use super::token::Token;
+
+/// The value a `catch` clause binds to its variable. `None` means `err` is 
+/// not an exception: `return`, `break` and `continue` pass through `catch`.
+pub fn caught_value(err: LoxRuntimeError) -> Result<Value, LoxRuntimeError> {
+    match err {
+        LoxRuntimeError::Throw(value, _) => Ok(value),
+        LoxRuntimeError::Error(err) => Ok(error_instance(&err)),
+        _ => Err(err),
+    }
+}
+
+/// Creates an `Error` instance from a native runtime error.
+pub fn error_instance(err: &LoxError) -> Value {
+    let klass = LoxClass::new("Error".to_string(), None, HashMap::new());
+    let mut instance = LoxInstance::new(klass);
+
+    instance.set_field("message", Value::String(err.err_msg().to_string()));
+    instance.set_field("line", Value::Number(err.line() as f64));
+
+    Value::LoxInstance(Rc::new(RefCell::new(instance)))
+}
+
+/// Reports a value thrown by the `throw` statement at `keyword`, for when no 
+/// `catch` clause catches it. `Error` instances report their own message and 
+/// line.
+pub fn uncaught(value: &Value, keyword: &Token) -> LoxError {
+    if let Value::LoxInstance(instance) = value {
+        let instance = instance.borrow();
+        if let (Some(Value::String(message)), Some(Value::Number(line))) = 
+            (instance.get_field("message"), instance.get_field("line")) {
+            return match line as usize {
+                0 => sys_error("", &message),
+                line => sys_error("", &format!("[line {}] Error: {}", line, message)),
+            };
+        }
+    }
+
+    error(keyword, &format!("Uncaught exception: {}.", value))
+}
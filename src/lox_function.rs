@@ -6,6 +6,7 @@
 //! 
 
 use std::any::Any;
+use std::path::Path;
 use std::rc::Rc;
 use std::cell::RefCell;
 
@@ -16,16 +17,25 @@ use super::value::Value;
 use super::environment::{Environment, EnvironmentRef};
 use super::lox_callable::LoxCallable;
 use super::lox_instance::LoxInstance;
+use super::stack_trace::StackFrame;
 
 pub struct LoxFunction {
     declaration: stmt::Function,
     closure: EnvironmentRef,
     is_initializer: bool,
+    // Not in the book: the imported module the function is declared in, for 
+    // its stack frames.
+    source: Option<Rc<Path>>,
 }
 
 impl LoxFunction {
     pub fn new(declaration: stmt::Function, closure: EnvironmentRef, is_initializer: bool) -> Self {
-        Self { declaration, closure, is_initializer }
+        Self { declaration, closure, is_initializer, source: None }
+    }
+
+    pub fn with_source(mut self, source: Option<Rc<Path>>) -> Self {
+        self.source = source;
+        self
     }
 
     pub fn bind(&self, instance: Rc<RefCell<LoxInstance>>) -> LoxFunction {
@@ -33,6 +43,48 @@ impl LoxFunction {
         env.define("this".to_string(), Value::LoxInstance(instance));
         LoxFunction::new(self.declaration.clone(), 
             Rc::new(RefCell::new(env)), self.is_initializer)
+            .with_source(self.source.clone())
+    }
+
+    pub fn name(&self) -> &str {
+        self.declaration.name().lexeme()
+    }
+
+    /// Its frame on the call stack.
+    pub fn frame(&self) -> StackFrame {
+        StackFrame::function(self.name()).with_source(self.source.clone())
+    }
+
+    // `call()` without a new frame on the call stack: `LoxClass::call()` 
+    // pushes the initializer's frame itself.
+    pub fn call_body(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        let environment = Rc::new(RefCell::new(
+            Environment::new_local_scope(Rc::clone(&self.closure))
+        ));
+
+        for (i, param) in self.declaration.params().iter().enumerate() {
+            let arg = arguments.get(i).unwrap_or(&Value::Nil).clone();
+            interpreter.allocate_entry(param.lexeme())?;
+            environment.borrow_mut().define(param.lexeme().to_string(), arg);
+        }
+
+        return match interpreter.execute_block(&self.declaration.body(), environment) {
+            Err(LoxRuntimeError::Return(ret)) => {
+                if self.is_initializer {
+                    Ok(Environment::get_at(&self.closure, 0, "this"))
+                } else {
+                    Ok(ret.value)
+                }
+            }
+            Err(err) => Err(err),
+            Ok(_) => {
+                if self.is_initializer {
+                    Ok(Environment::get_at(&self.closure, 0, "this"))
+                } else {
+                    Ok(Value::Nil)
+                }
+            }
+        }
     }
 }
 
@@ -64,6 +116,7 @@ impl Clone for LoxFunction {
             declaration: self.declaration.clone(),
             closure: Rc::clone(&self.closure), // Shallow clone.
             is_initializer: self.is_initializer,
+            source: self.source.clone(),
         }
     }
 }
@@ -74,32 +127,8 @@ impl LoxCallable for LoxFunction {
     }
 
     fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
-        let environment = Rc::new(RefCell::new(
-            Environment::new_local_scope(Rc::clone(&self.closure))
-        ));
-
-        for (i, param) in self.declaration.params().iter().enumerate() {
-            let arg = arguments.get(i).unwrap_or(&Value::Nil).clone();
-            environment.borrow_mut().define(param.lexeme().to_string(), arg);
-        }
-
-        return match interpreter.execute_block(&self.declaration.body(), environment) {
-            Err(LoxRuntimeError::Return(ret)) => {
-                if self.is_initializer {
-                    Ok(Environment::get_at(&self.closure, 0, "this"))
-                } else {
-                    Ok(ret.value)
-                }
-            }
-            Err(LoxRuntimeError::Error(err)) => Err(LoxRuntimeError::Error(err)),
-            Ok(_) => {
-                if self.is_initializer {
-                    Ok(Environment::get_at(&self.closure, 0, "this"))
-                } else {
-                    Ok(Value::Nil)
-                }
-            }
-        }
+        interpreter.in_frame(self.frame(), 
+            |interpreter| self.call_body(interpreter, arguments))
     }
 
     fn as_any(&self) -> &dyn Any {
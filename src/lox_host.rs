@@ -0,0 +1,134 @@
+/* Date Created: 17/10/2026. */
+
+//! Rust-specific. Rust objects handed to scripts: they have properties and
+//! methods, like instances of Lox classes. E.g. a counter:
+//!
+//! ```
+//! use std::cell::Cell;
+//! use rlox::interpreter::Interpreter;
+//! use rlox::lox_host::HostObject;
+//! use rlox::lox_native::Arity;
+//! use rlox::lox_runtime_error::LoxRuntimeError;
+//! use rlox::value::Value;
+//!
+//! struct Counter { count: Cell<f64> }
+//!
+//! impl HostObject for Counter {
+//!     fn name(&self) -> &str { "Counter" }
+//!
+//!     fn get(&self, name: &str) -> Option<Value> {
+//!         (name == "count").then(|| Value::Number(self.count.get()))
+//!     }
+//!
+//!     fn method_arity(&self, name: &str) -> Option<Arity> {
+//!         (name == "increment").then_some(Arity::Fixed(0))
+//!     }
+//!
+//!     fn call_method(&self, _: &mut Interpreter, _: &str, _: &[Value]) -> Result<Value, LoxRuntimeError> {
+//!         self.count.set(self.count.get() + 1.0);
+//!         Ok(Value::Nil)
+//!     }
+//! }
+//! ```
+//!
+//! A script then uses `counter.increment()` and `counter.count`, and `print
+//! counter` prints `Counter instance`. Only the tree-walker supports them.
+
+use std::fmt;
+use std::rc::Rc;
+
+use super::value::Value;
+use super::lox_error::LoxError;
+use super::lox_error_helper::{error, sys_error};
+use super::lox_runtime_error::LoxRuntimeError;
+use super::lox_callable::LoxCallable;
+use super::lox_native::Arity;
+use super::interpreter::Interpreter;
+use super::stack_trace::StackFrame;
+use super::token::Token;
+
+pub trait HostObject {
+    /// The class name: printed as `<name> instance`.
+    fn name(&self) -> &str;
+
+    /// The property `name`, `None` if there is no such property.
+    fn get(&self, name: &str) -> Option<Value>;
+
+    /// Properties are read-only unless this is implemented. Uses interior
+    /// mutability, since scripts share the object.
+    fn set(&self, name: &str, _value: Value) -> Result<(), LoxRuntimeError> {
+        Err(sys_error("", &format!("Can't set property '{}' of {}.", name, self.name())).into())
+    }
+
+    /// The arity of the method `name`, `None` if there is no such method.
+    fn method_arity(&self, _name: &str) -> Option<Arity> {
+        None
+    }
+
+    /// Only called for methods `method_arity()` knows, with the right number
+    /// of arguments.
+    fn call_method(&self, _interpreter: &mut Interpreter, name: &str,
+        _arguments: &[Value]) -> Result<Value, LoxRuntimeError> {
+        Err(sys_error("", &format!("Undefined method '{}'.", name)).into())
+    }
+}
+
+/// `object.name`: a property, or a method bound to the object. Properties
+/// shadow methods, like fields do in `LoxInstance::get()`.
+pub fn get(object: &Rc<dyn HostObject>, name: &Token) -> Result<Value, LoxError> {
+    if let Some(value) = object.get(name.lexeme()) {
+        return Ok(value);
+    }
+
+    if let Some(arity) = object.method_arity(name.lexeme()) {
+        return Ok(Value::LoxCallable(Rc::new(HostMethod {
+            object: Rc::clone(object), name: name.lexeme().to_string(), arity })));
+    }
+
+    Err(error(name, &format!("Undefined property '{}'.", name.lexeme())))
+}
+
+// A method bound to its object.
+pub struct HostMethod {
+    object: Rc<dyn HostObject>,
+    name: String,
+    arity: Arity,
+}
+
+impl LoxCallable for HostMethod {
+    fn arity(&self) -> usize {
+        match self.arity {
+            Arity::Fixed(arity) => arity,
+            Arity::Variadic => 0,
+        }
+    }
+
+    fn is_variadic(&self) -> bool {
+        self.arity == Arity::Variadic
+    }
+
+    fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        interpreter.in_frame(StackFrame::native(&self.name),
+            |interpreter| self.object.call_method(interpreter, &self.name, &arguments))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl fmt::Debug for HostMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostMethod")
+            .field("object", &self.object.name())
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .finish()
+    }
+}
+
+impl fmt::Display for HostMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<native fn {}>", self.name)
+    }
+}
@@ -52,7 +52,17 @@ impl LoxInstance {
     }
 
     pub fn set(&mut self, name: &Token, value: Value) {
-        self.fields.insert(name.lexeme().to_string(), value);
+        self.set_field(name.lexeme(), value);
+    }
+
+    // Rust-specific: field access for instances created natively, where 
+    // there is no Token. See ./src/lox_exception.rs.
+    pub fn get_field(&self, name: &str) -> Option<Value> {
+        self.fields.get(name).cloned()
+    }
+
+    pub fn set_field(&mut self, name: &str, value: Value) {
+        self.fields.insert(name.to_string(), value);
     }
 }
 
@@ -0,0 +1,124 @@
+/* Date Created: 17/10/2026. */
+
+//! Not in the book. Native methods of the Lox list type,
+//! [`Value::List`](`crate::value::Value::List`).
+//!
+//! `xs.push` in `xs.push(4);` is an `Expr::Get`: `Interpreter::visit_get_expr()`
+//! calls [`LoxListMethod::get`] to bind the method to the list, the same way
+//! [`LoxInstance::get`](`crate::lox_instance::LoxInstance::get`) binds a class
+//! method to its instance.
+
+use std::any::Any;
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use super::lox_runtime_error::LoxRuntimeError;
+use super::lox_error::LoxError;
+use super::lox_error_helper::{error, runtime_error};
+use super::interpreter::Interpreter;
+use super::value::Value;
+use super::lox_callable::LoxCallable;
+use super::stack_trace::StackFrame;
+use super::token::Token;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListMethod {
+    Push,
+    Pop,
+    Len,
+    Insert,
+    Remove,
+}
+
+/// A native list method bound to the list it was accessed on.
+pub struct LoxListMethod {
+    list: Rc<RefCell<Vec<Value>>>,
+    // The property name token: used to report runtime errors.
+    name: Token,
+    method: ListMethod,
+}
+
+impl LoxListMethod {
+    // list comes from the `Interpreter::visit_get_expr()` method.
+    pub fn get(list: Rc<RefCell<Vec<Value>>>, name: &Token) -> Result<Value, LoxError> {
+        let method = match name.lexeme() {
+            "push" => ListMethod::Push,
+            "pop" => ListMethod::Pop,
+            "len" => ListMethod::Len,
+            "insert" => ListMethod::Insert,
+            "remove" => ListMethod::Remove,
+            _ => return Err(error(name, &format!("Undefined property '{}'.", name.lexeme()))),
+        };
+
+        Ok(Value::LoxCallable(Rc::new(LoxListMethod { list, name: name.clone(), method })))
+    }
+
+    /// Converts a Lox number into a list position in `0..end`. `end` is the list 
+    /// length, except for `insert()` which also accepts the position past the end.
+    pub fn to_index(token: &Token, index: &Value, end: usize) -> Result<usize, LoxRuntimeError> {
+        match index {
+            Value::Number(n) if n.fract() != 0.0 =>
+                Err(runtime_error(token, "List index must be an integer.")),
+            Value::Number(n) if *n < 0.0 || *n >= end as f64 =>
+                Err(runtime_error(token, "List index out of range.")),
+            Value::Number(n) => Ok(*n as usize),
+            _ => Err(runtime_error(token, "List index must be a number.")),
+        }
+    }
+
+    // The method itself: `call()` runs it in a native frame of the call stack.
+    fn call_native(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        let mut list = self.list.borrow_mut();
+
+        match self.method {
+            ListMethod::Push => {
+                interpreter.allocate_values(1)?;
+                list.push(arguments[0].clone());
+                Ok(Value::Nil)
+            }
+            ListMethod::Pop => list.pop()
+                .ok_or_else(|| runtime_error(&self.name, "Can't pop from an empty list.")),
+            ListMethod::Len => Ok(Value::Number(list.len() as f64)),
+            ListMethod::Insert => {
+                let index = Self::to_index(&self.name, &arguments[0], list.len() + 1)?;
+                interpreter.allocate_values(1)?;
+                list.insert(index, arguments[1].clone());
+                Ok(Value::Nil)
+            }
+            ListMethod::Remove => {
+                let index = Self::to_index(&self.name, &arguments[0], list.len())?;
+                Ok(list.remove(index))
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for LoxListMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LoxListMethod {{ name: {} }}", self.name.lexeme())
+    }
+}
+
+impl LoxCallable for LoxListMethod {
+    fn arity(&self) -> usize {
+        match self.method {
+            ListMethod::Pop | ListMethod::Len => 0,
+            ListMethod::Push | ListMethod::Remove => 1,
+            ListMethod::Insert => 2,
+        }
+    }
+
+    fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        interpreter.in_frame(StackFrame::native(self.name.lexeme()), |interpreter| self.call_native(interpreter, arguments))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl std::fmt::Display for LoxListMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<native fn {}>", self.name.lexeme())
+    }
+}
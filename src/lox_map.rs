@@ -0,0 +1,204 @@
+/* Date Created: 17/10/2026. */
+
+//! Not in the book. The Lox map type, [`Value::Map`](`crate::value::Value::Map`),
+//! and its native methods.
+//!
+//! Entries keep their insertion order, so that `keys()`, `values()` and `print`
+//! give the same result on every run.
+//!
+//! `m.keys` in `m.keys();` is an `Expr::Get`: `Interpreter::visit_get_expr()`
+//! calls [`LoxMapMethod::get`] to bind the method to the map, the same way
+//! [`LoxListMethod::get`](`crate::lox_list::LoxListMethod::get`) does for lists.
+
+use std::any::Any;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::fmt;
+
+use super::lox_runtime_error::LoxRuntimeError;
+use super::lox_error::LoxError;
+use super::lox_error_helper::{error, runtime_error};
+use super::interpreter::Interpreter;
+use super::value::Value;
+use super::lox_callable::LoxCallable;
+use super::stack_trace::StackFrame;
+use super::token::Token;
+
+// Generic over the value type so that the bytecode VM, see ./src/vm/, shares 
+// it with the tree-walking interpreter.
+#[derive(Debug, Clone)]
+pub struct LoxMap<V = Value> {
+    entries: Vec<(V, V)>,
+    // Key to position in `entries`.
+    indexes: HashMap<V, usize>,
+}
+
+impl LoxMap {
+    /// Only numbers, strings and booleans can be used as keys. NaN is not 
+    /// equal to itself, so it could never be found again.
+    pub fn check_key(token: &Token, key: &Value) -> Result<(), LoxRuntimeError> {
+        match key {
+            Value::Number(n) if n.is_nan() => Err(runtime_error(token, "Map keys can't be NaN.")),
+            Value::Number(_) | Value::String(_) | Value::Boolean(_) => Ok(()),
+            _ => Err(runtime_error(token, "Map keys must be strings, numbers or booleans.")),
+        }
+    }
+}
+
+impl<V: Clone + Eq + Hash> LoxMap<V> {
+    pub fn new() -> Self {
+        LoxMap { entries: Vec::new(), indexes: HashMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &V) -> Option<V> {
+        self.indexes.get(key).map(|&i| self.entries[i].1.clone())
+    }
+
+    pub fn contains_key(&self, key: &V) -> bool {
+        self.indexes.contains_key(key)
+    }
+
+    pub fn insert(&mut self, key: V, value: V) {
+        if let Some(&i) = self.indexes.get(&key) {
+            self.entries[i].1 = value;
+        } else {
+            self.indexes.insert(key.clone(), self.entries.len());
+            self.entries.push((key, value));
+        }
+    }
+
+    /// Removes the entry of `key` and keeps the order of the others. This 
+    /// takes time in proportion to the size of the map: the entries after it 
+    /// move down by one, and every position in `indexes` is checked.
+    pub fn remove(&mut self, key: &V) -> Option<V> {
+        let i = self.indexes.remove(key)?;
+        let (_, value) = self.entries.remove(i);
+
+        // Entries after the removed one have moved down by one.
+        for index in self.indexes.values_mut() {
+            if *index > i {
+                *index -= 1;
+            }
+        }
+
+        Some(value)
+    }
+
+    pub fn entries(&self) -> &Vec<(V, V)> {
+        &self.entries
+    }
+
+    pub fn keys(&self) -> Vec<V> {
+        self.entries.iter().map(|(key, _)| key.clone()).collect()
+    }
+
+    pub fn values(&self) -> Vec<V> {
+        self.entries.iter().map(|(_, value)| value.clone()).collect()
+    }
+}
+
+impl<V: Clone + Eq + Hash> Default for LoxMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MapMethod {
+    Keys,
+    Values,
+    Has,
+    Remove,
+    Len,
+}
+
+/// A native map method bound to the map it was accessed on.
+pub struct LoxMapMethod {
+    map: Rc<RefCell<LoxMap>>,
+    // The property name token: used to report runtime errors.
+    name: Token,
+    method: MapMethod,
+}
+
+impl LoxMapMethod {
+    // map comes from the `Interpreter::visit_get_expr()` method.
+    pub fn get(map: Rc<RefCell<LoxMap>>, name: &Token) -> Result<Value, LoxError> {
+        let method = match name.lexeme() {
+            "keys" => MapMethod::Keys,
+            "values" => MapMethod::Values,
+            "has" => MapMethod::Has,
+            "remove" => MapMethod::Remove,
+            "len" => MapMethod::Len,
+            _ => return Err(error(name, &format!("Undefined property '{}'.", name.lexeme()))),
+        };
+
+        Ok(Value::LoxCallable(Rc::new(LoxMapMethod { map, name: name.clone(), method })))
+    }
+
+    // The method itself: `call()` runs it in a native frame of the call stack.
+    fn call_native(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        let mut map = self.map.borrow_mut();
+
+        match self.method {
+            // New lists.
+            MapMethod::Keys => {
+                interpreter.allocate_values(map.len())?;
+                Ok(Value::List(Rc::new(RefCell::new(map.keys()))))
+            }
+            MapMethod::Values => {
+                interpreter.allocate_values(map.len())?;
+                Ok(Value::List(Rc::new(RefCell::new(map.values()))))
+            }
+            MapMethod::Has => {
+                LoxMap::check_key(&self.name, &arguments[0])?;
+                Ok(Value::Boolean(map.contains_key(&arguments[0])))
+            }
+            MapMethod::Remove => {
+                LoxMap::check_key(&self.name, &arguments[0])?;
+                map.remove(&arguments[0]).ok_or_else(|| runtime_error(&self.name,
+                    &format!("Undefined key '{}'.", interpreter.stringify(&arguments[0]))))
+            }
+            MapMethod::Len => Ok(Value::Number(map.len() as f64)),
+        }
+    }
+}
+
+impl fmt::Debug for LoxMapMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LoxMapMethod {{ name: {} }}", self.name.lexeme())
+    }
+}
+
+impl LoxCallable for LoxMapMethod {
+    fn arity(&self) -> usize {
+        match self.method {
+            MapMethod::Keys | MapMethod::Values | MapMethod::Len => 0,
+            MapMethod::Has | MapMethod::Remove => 1,
+        }
+    }
+
+    fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        interpreter.in_frame(StackFrame::native(self.name.lexeme()), |interpreter| self.call_native(interpreter, arguments))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl fmt::Display for LoxMapMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<native fn {}>", self.name.lexeme())
+    }
+}
@@ -0,0 +1,56 @@
+/* Date Created: 17/10/2026. */
+
+//! Not in the book. The Lox module type, 
+//! [`Value::Module`](`crate::value::Value::Module`).
+//!
+//! `import "lib/math.lox";` runs `math.lox` once, in its own global 
+//! environment, and binds a module named `math`. `math.square` in 
+//! `math.square(3);` is an `Expr::Get`: `Interpreter::visit_get_expr()` calls 
+//! [`LoxModule::get`] to look `square` up in the module's globals.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use super::lox_error::LoxError;
+use super::lox_error_helper::error;
+use super::environment::EnvironmentRef;
+use super::stmt::Stmt;
+use super::value::Value;
+use super::token::Token;
+
+pub struct LoxModule {
+    name: String,
+    // Canonical path: the key of the interpreter's module cache.
+    path: PathBuf,
+    globals: EnvironmentRef,
+    // The interpreter's resolved locals are keyed by `Expr` addresses: the 
+    // module's syntax tree must live as long as the module does.
+    #[allow(dead_code)]
+    statements: Vec<Rc<Stmt>>,
+}
+
+impl LoxModule {
+    pub fn new(name: String, path: PathBuf, 
+        globals: EnvironmentRef, statements: Vec<Rc<Stmt>>) -> Self {
+        LoxModule { name, path, globals, statements }
+    }
+
+    pub fn get(&self, name: &Token) -> Result<Value, LoxError> {
+        self.globals.borrow().values().get(name.lexeme())
+            .cloned()
+            .ok_or_else(|| error(name, &format!("Undefined property '{}'.", name.lexeme())))
+    }
+}
+
+impl fmt::Debug for LoxModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LoxModule {{ name: {}, path: {:?} }}", self.name, self.path)
+    }
+}
+
+impl fmt::Display for LoxModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<module {}>", self.name)
+    }
+}
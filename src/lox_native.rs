@@ -0,0 +1,108 @@
+/* Date Created: 17/10/2026. */
+
+//! Rust-specific. Native functions which Rust programs embedding Lox define,
+//! with `Interpreter::define_native()`: a Rust closure, with a name and an
+//! arity. E.g.:
+//!
+//! ```
+//! use rlox::interpreter::Interpreter;
+//! use rlox::lox_native::Arity;
+//! use rlox::value::Value;
+//!
+//! let mut interpreter = Interpreter::new(std::io::stdout());
+//! interpreter.define_native("sum", Arity::Variadic, |_, arguments| {
+//!     Ok(Value::Number(arguments.iter()
+//!         .map(|value| if let Value::Number(n) = value { *n } else { 0.0 })
+//!         .sum()))
+//! });
+//! ```
+//!
+//! They print as `<native fn sum>`. Only the tree-walker supports them. The 
+//! book's `clock()` is one too.
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::value::Value;
+use super::lox_runtime_error::LoxRuntimeError;
+use super::lox_callable::LoxCallable;
+use super::interpreter::Interpreter;
+use super::stack_trace::StackFrame;
+
+pub type NativeFn = dyn Fn(&mut Interpreter, &[Value]) -> Result<Value, LoxRuntimeError>;
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arity {
+    Fixed(usize),
+    // Any number of arguments.
+    Variadic,
+}
+
+pub struct NativeFunction {
+    name: String,
+    arity: Arity,
+    function: Box<NativeFn>,
+}
+
+impl NativeFunction {
+    pub fn new(name: &str, arity: Arity,
+        function: impl Fn(&mut Interpreter, &[Value]) -> Result<Value, LoxRuntimeError> + 'static) -> Self {
+        NativeFunction { name: name.to_string(), arity, function: Box::new(function) }
+    }
+
+    #[allow(dead_code)]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl LoxCallable for NativeFunction {
+    fn arity(&self) -> usize {
+        match self.arity {
+            Arity::Fixed(arity) => arity,
+            Arity::Variadic => 0,
+        }
+    }
+
+    fn is_variadic(&self) -> bool {
+        self.arity == Arity::Variadic
+    }
+
+    fn call(&self, interpreter: &mut Interpreter, arguments: Vec<Value>) -> Result<Value, LoxRuntimeError> {
+        interpreter.in_frame(StackFrame::native(&self.name),
+            |interpreter| (self.function)(interpreter, &arguments))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Chapter 10: Functions, the 
+/// [Native Functions](https://craftinginterpreters.com/functions.html#native-functions)
+/// section. The seconds since the Unix epoch, for benchmarks.
+pub fn clock(_: &mut Interpreter, _: &[Value]) -> Result<Value, LoxRuntimeError> {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards");
+
+    let seconds = since_epoch.as_secs() as f64 + since_epoch.subsec_micros() as f64 / 1_000_000.0;
+
+    Ok(Value::Number(seconds))
+}
+
+impl fmt::Debug for NativeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeFunction")
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .finish()
+    }
+}
+
+impl fmt::Display for NativeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<native fn {}>", self.name)
+    }
+}
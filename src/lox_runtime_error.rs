@@ -11,6 +11,7 @@ use std::fmt;
 
 use super::lox_error::LoxError;
 use super::lox_return::LoxReturn;
+use super::value::Value;
 
 // `Return.java` creates a custom unchecked exception to signal early exits 
 // from Lox functions. In Rust, we use **control flow via `Result` and early 
@@ -29,10 +30,26 @@ use super::lox_return::LoxReturn;
 //        }
 //    };
 //
+// `break` and `continue` use the same mechanism: they unwind through 
+// `execute_block()` until `Interpreter::visit_while_stmt()` catches them. The 
+// resolver guarantees they never appear outside a loop.
+//
+// `throw` unwinds as `Throw` until `Interpreter::visit_try_stmt()` catches it. 
+// Unlike `break` and `continue`, nothing guarantees a `Throw` is caught: at the 
+// top level it becomes an ordinary `LoxError`, the one it carries, made where 
+// the value was thrown, see `lox_exception::uncaught()`.
+//
+// `Abort` stops the script when the host's limits are exceeded, see 
+// `Interpreter::with_step_budget()`. `catch` does not catch it: it unwinds 
+// all the way to the top level.
 #[derive(Debug)]
 pub enum LoxRuntimeError {
     Error(LoxError),
+    Abort(LoxError),
     Return(LoxReturn),
+    Break,
+    Continue,
+    Throw(Value, Box<LoxError>),
 }
 
 impl From<LoxError> for LoxRuntimeError {
@@ -44,7 +61,8 @@ impl From<LoxError> for LoxRuntimeError {
 impl From<LoxRuntimeError> for LoxError {
     fn from(error: LoxRuntimeError) -> Self {
         let inner = match error {
-            LoxRuntimeError::Error(e) => e,
+            LoxRuntimeError::Error(e) | LoxRuntimeError::Abort(e) => e,
+            LoxRuntimeError::Throw(_, err) => *err,
             _ => unreachable!("Expected RuntimeError::Error"),
         };
         inner
@@ -54,8 +72,11 @@ impl From<LoxRuntimeError> for LoxError {
 impl fmt::Display for LoxRuntimeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            LoxRuntimeError::Error(err) => write!(f, "{}", err),
+            LoxRuntimeError::Error(err) | LoxRuntimeError::Abort(err) => write!(f, "{}", err),
             LoxRuntimeError::Return(ret) => write!(f, "Return: {}", ret),
+            LoxRuntimeError::Break => write!(f, "Break"),
+            LoxRuntimeError::Continue => write!(f, "Continue"),
+            LoxRuntimeError::Throw(_, err) => write!(f, "{}", err),
         }
     }
 }
@@ -7,6 +7,7 @@ use std::env;
 use std::process;
 use std::fs::{read_to_string, exists};
 use std::io::{self, Write};
+use std::thread;
 
 mod expr;
 mod stmt;
@@ -17,7 +18,6 @@ mod token_type;
 mod token;
 mod value;
 mod lox_callable;
-mod lox_clock;
 mod scanner;
 mod ast_printer;
 mod parser;
@@ -29,66 +29,168 @@ mod lox_runtime_error;
 mod resolver;
 mod lox_class;
 mod lox_instance;
+mod lox_list;
+mod lox_map;
+mod lox_exception;
+mod lox_module;
+mod lox_native;
+mod lox_host;
+mod cancel_token;
+mod vm;
+mod diagnostics;
+mod stack_trace;
 
 use rlox::{unwrap_expr, unwrap_stmt};
 
 use scanner::Scanner;
 use interpreter::Interpreter;
 use resolver::Resolver;
-use lox_error::LoxError;
-
-fn print_error(err: LoxError, originator: &str) {
-    println!("{} error:", originator);
-
-    let err_msgs: Vec<String> = err
-        .to_string()
-        .lines()
-        .map(|line| line.to_string())
-        .collect();
-    for err_msg in err_msgs {
-        println!("    {}", err_msg);
+use lox_error::{LoxError, Phase};
+use vm::machine::Vm;
+use diagnostics::{ColorChoice, Diagnostics};
+
+// Command line options.
+struct Options {
+    // --vm: compile to bytecode and run on the VM, see ./src/vm/mod.rs.
+    use_vm: bool,
+    // --color=always|never|auto: colours error messages.
+    color: ColorChoice,
+    // --max-call-depth=N: see Interpreter::set_max_call_depth().
+    max_call_depth: Option<usize>,
+    // --max-nesting=N: see Parser::set_max_nesting().
+    max_nesting: Option<usize>,
+}
+
+// Every call in the tree-walker, and every nesting level in the parser, the 
+// resolver and the tree-walker, takes Rust stack: in debug builds over 10KB for 
+// even a small function. The script runs on a thread with room for both limits.
+const STACK_SIZE_PER_CALL: usize = 32 * 1024;
+const STACK_SIZE_PER_NESTING: usize = 64 * 1024;
+
+// The interpreter's call depth limit unless --max-call-depth=N is given, and 
+// the parser's nesting limit unless --max-nesting=N is given: the library 
+// defaults, Interpreter::DEFAULT_MAX_CALL_DEPTH and Parser::DEFAULT_MAX_NESTING, 
+// fit a standard thread.
+const CLI_MAX_CALL_DEPTH: usize = 1024;
+const CLI_MAX_NESTING: usize = 256;
+
+// The stack the calls in progress may take, see Interpreter::set_max_stack().
+fn call_stack_size(options: &Options) -> usize {
+    options.max_call_depth.unwrap_or(CLI_MAX_CALL_DEPTH).max(CLI_MAX_CALL_DEPTH)
+        .saturating_mul(STACK_SIZE_PER_CALL)
+}
+
+// The stack of the thread the script runs on: the calls, and the nesting of 
+// the source on top of them.
+fn stack_size(options: &Options) -> usize {
+    let max_nesting = options.max_nesting.unwrap_or(CLI_MAX_NESTING).max(CLI_MAX_NESTING);
+    call_stack_size(options).saturating_add(max_nesting.saturating_mul(STACK_SIZE_PER_NESTING))
+}
+
+// Exit codes: the input data is incorrect, an internal software error, here a 
+// runtime error, and an input/output error.
+const EX_DATAERR: i32 = 65;
+const EX_SOFTWARE: i32 = 70;
+const EX_IOERR: i32 = 74;
+
+// The value of a --max-call-depth= or --max-nesting= option.
+fn parse_limit(option: &str, value: &str) -> usize {
+    match value.parse::<usize>() {
+        Ok(limit) if limit > 0 => limit,
+        _ => {
+            eprintln!("Invalid {} value `{}`: expected a positive number.", option, value);
+            process::exit(1);
+        }
+    }
+}
+
+fn print_error(diagnostics: &Diagnostics, err: &LoxError, originator: &str) {
+    eprint!("{}", diagnostics.render(originator, err));
+}
+
+// The exit code for an error of `phase`, following jlox and clox, which use 
+// the BSD sysexits.h codes.
+fn exit_code(phase: Option<Phase>) -> i32 {
+    match phase {
+        Some(Phase::Io) => EX_IOERR,
+        Some(Phase::Scan | Phase::Parse | Phase::Resolve) => EX_DATAERR,
+        Some(Phase::Runtime) | None => EX_SOFTWARE,
     }
 }
 
-fn run(source: &str) -> Result<(), std::io::Error> {
+// script is None for the REPL: imports then resolve against the current directory.
+//
+// Errors are printed, and returned tagged with the phase which reports them.
+fn run(source: &str, script: Option<&str>, options: &Options) -> Result<(), LoxError> {
+    let diagnostics = Diagnostics::new(source, script.unwrap_or("<repl>"), options.color.enabled());
+    let report = |err: LoxError, phase: Phase, originator: &str| {
+        print_error(&diagnostics, &err, originator);
+        err.with_phase(phase)
+    };
+
     let mut scanner = Scanner::new(source);
-    match scanner.scan_tokens() {
-        Err(err) => print_error(err, "Scanner"),
-        Ok(tokens) => {
-            let mut parser = parser::Parser::new(&tokens);
-            match parser.parse() {
-                Err(err) => print_error(err, "Parser"),
-                Ok(statements) => { 
-                    // Both are valid.
-                    // let mut interpreter = Interpreter::new(Box::new(io::stdout()));
-                    let mut interpreter = Interpreter::new(io::stdout());
-                    let mut resolver: Resolver = Resolver::new(&mut interpreter);
-
-                    match resolver.resolve(&statements) {
-                        Err(err) => print_error(err.into(), "Resolver"),
-                        Ok(_) => {
-                            match interpreter.interpret(&statements) {
-                                Err(err) => print_error(err.into(), "Interpreter"),
-                                Ok(_) => (),
-                            }
-                        }
-                    }
-                },
-            }
-        },
+    let (tokens, scan_error) = scanner.scan_tokens_with_errors();
+    let scan_error = scan_error.map(|err| report(err, Phase::Scan, "Scanner"));
+
+    let mut parser = parser::Parser::new(&tokens);
+    parser.set_max_nesting(options.max_nesting.unwrap_or(CLI_MAX_NESTING));
+    let parsed = parser.parse();
+
+    // The tokens of a source with scanner errors are parsed too, so that all 
+    // errors are reported in one run, but nothing runs.
+    if let Some(err) = scan_error {
+        if let Err(parse_err) = parsed {
+            eprintln!();
+            print_error(&diagnostics, &parse_err, "Parser");
+        }
+        return Err(err);
     }
+    let statements = parsed
+        .map_err(|err| report(err, Phase::Parse, "Parser"))?;
 
-    Ok(())
+    // Both are valid.
+    // let mut interpreter = Interpreter::new(Box::new(io::stdout()));
+    let mut interpreter = Interpreter::new(io::stdout());
+    interpreter.set_max_call_depth(options.max_call_depth.unwrap_or(CLI_MAX_CALL_DEPTH));
+    interpreter.set_max_stack(call_stack_size(options));
+    if let Some(script) = script {
+        interpreter.set_script_path(script);
+    }
+    let mut resolver: Resolver = Resolver::new(&mut interpreter);
+    resolver.resolve(&statements)
+        .map_err(|err| report(err.into(), Phase::Resolve, "Resolver"))?;
+
+    if options.use_vm {
+        let mut vm = Vm::new(io::stdout());
+        if let Some(depth) = options.max_call_depth {
+            vm.set_max_call_depth(depth);
+        }
+        if let Some(script) = script {
+            vm.set_script_path(script);
+        }
+        vm.interpret(&statements).map_err(|err| {
+            vm.report_error(&diagnostics.render("VM", &err));
+            err.with_phase(Phase::Runtime)
+        })
+    } else {
+        interpreter.interpret(&statements).map_err(|err| {
+            interpreter.report_error(&diagnostics.render("Interpreter", &err));
+            err.with_phase(Phase::Runtime)
+        })
+    }
 }
 
-pub fn run_file(scriptfile: &str) -> Result<(), std::io::Error> {
-    let contents = read_to_string(scriptfile)?;
-    run(&contents)?;
+fn run_file(scriptfile: &str, options: &Options) -> Result<(), LoxError> {
+    let contents = read_to_string(scriptfile).map_err(|err| {
+        let err = LoxError::new(0, "", &format!("Can't read '{}': {}.", scriptfile, err));
+        eprintln!("{}", err);
+        err.with_phase(Phase::Io)
+    })?;
 
-    Ok(())
+    run(&contents, Some(scriptfile), options)
 }
 
-pub fn run_prompt() -> Result<(), std::io::Error> {
+fn run_prompt(options: &Options) -> Result<(), std::io::Error> {
     let mut line = String::new();
 
     loop {
@@ -105,7 +207,8 @@ pub fn run_prompt() -> Result<(), std::io::Error> {
             break;
         }
 
-        run(&line)?;
+        // The errors are printed: the REPL carries on.
+        let _ = run(&line, None, options);
 
         // Empty the string.
         line.clear();
@@ -116,19 +219,62 @@ pub fn run_prompt() -> Result<(), std::io::Error> {
 
 fn main() {
     // Collect command line arguments.
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+
+    let mut options = Options { 
+        use_vm: false, 
+        color: ColorChoice::Auto, 
+        max_call_depth: None, 
+        max_nesting: None,
+    };
+    for arg in args.iter().skip(1) {
+        if arg == "--vm" {
+            options.use_vm = true;
+        } else if let Some(value) = arg.strip_prefix("--color=") {
+            options.color = ColorChoice::parse(value).unwrap_or_else(|| {
+                eprintln!("Invalid --color value `{}`: expected always, never or auto.", value);
+                process::exit(1);
+            });
+        } else if let Some(value) = arg.strip_prefix("--max-call-depth=") {
+            options.max_call_depth = Some(parse_limit("--max-call-depth", value));
+        } else if let Some(value) = arg.strip_prefix("--max-nesting=") {
+            options.max_nesting = Some(parse_limit("--max-nesting", value));
+        }
+    }
+    args.retain(|arg| arg != "--vm" && !arg.starts_with("--color=") 
+        && !arg.starts_with("--max-call-depth=") && !arg.starts_with("--max-nesting="));
 
     if args.len() > 2 {
-        println!("Usage: {} [script]", &args[0]);
+        println!("Usage: {} [--vm] [--color=always|never|auto] [--max-call-depth=N] [--max-nesting=N] [script]", 
+            &args[0]);
         process::exit(1);
-    } else if args.len() == 2 {
-        if !exists(&args[1]).expect("Can not check if input file exists.") {
-            println!("Input file `{}` does not exist!", &args[1]);
-            process::exit(65);
-        }
+    } else if args.len() == 2 && !exists(&args[1]).expect("Can not check if input file exists.") {
+        eprintln!("Input file `{}` does not exist!", &args[1]);
+        process::exit(EX_IOERR);
+    }
 
-        let _ = run_file(&args[1]);
-    } else {
-        let _ = run_prompt();
+    let stack_size = stack_size(&options);
+
+    let runner = thread::Builder::new()
+        .stack_size(stack_size)
+        .spawn(move || {
+            if args.len() == 2 {
+                run_file(&args[1], &options).map_or_else(|err| exit_code(err.phase()), |_| 0)
+            } else {
+                run_prompt(&options).map_or_else(|err| {
+                    eprintln!("{}", err);
+                    EX_IOERR
+                }, |_| 0)
+            }
+        });
+
+    match runner {
+        // A panic is an internal software error.
+        Ok(runner) => process::exit(runner.join().unwrap_or(EX_SOFTWARE)),
+        Err(err) => {
+            eprintln!("Can not reserve {} bytes of stack for --max-call-depth and --max-nesting: {}", 
+                stack_size, err);
+            process::exit(1);
+        }
     }
 }
\ No newline at end of file
@@ -14,15 +14,27 @@ use crate::stmt;
 
 use super::lox_error::LoxError;
 use super::token_type::TokenType;
-use super::token::{LiteralValue, LiteralValue::*, Token};
-use super::lox_error_helper::{error, sys_error}; 
+use super::token::{LiteralValue, LiteralValue::*, Span, Token};
+use super::lox_error_helper::error; 
+use super::scanner::Scanner;
 use super::expr::*;
 use super::stmt::*;
 use super::unwrap_stmt;
 
+/// Not in the book: how deeply expressions and statements may nest. Without a 
+/// limit, deeply nested source overflows the Rust stack, in the parser or 
+/// later in the resolver or the interpreter. Each pair of parentheses takes 
+/// about 30KB of stack in the parser in a debug build: 48 levels fit a 
+/// standard 2MB thread. A deeper limit needs a thread with a larger stack, see 
+/// ./src/main.rs.
+pub const DEFAULT_MAX_NESTING: usize = 48;
+
 pub struct Parser<'a> {
     tokens: &'a Vec<Token>,
     current: usize,
+    // The current nesting depth, and its limit.
+    nesting: usize,
+    max_nesting: usize,
 }
 
 impl<'a> Parser<'a> {
@@ -30,9 +42,44 @@ impl<'a> Parser<'a> {
         Parser {
             tokens,
             current: 0,
+            nesting: 0,
+            max_nesting: DEFAULT_MAX_NESTING,
         }
     }
 
+    pub fn set_max_nesting(&mut self, max_nesting: usize) {
+        self.max_nesting = max_nesting;
+    }
+
+    // Runs `parse` one nesting level deeper, or reports an error at the 
+    // current token if that is beyond the limit.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, LoxError>) -> Result<T, LoxError> {
+        if self.nesting >= self.max_nesting {
+            return Err(error(self.peek(), "Too much nesting."));
+        }
+
+        let nesting = self.nesting;
+        self.nesting += 1;
+        let result = parse(self);
+        // Also gives back the levels of the operator chains `parse` has read.
+        self.nesting = nesting;
+        result
+    }
+
+    // Binary and logical operators are left-associative, and so are calls, 
+    // property accesses and indexing: each one in a chain nests the chain one 
+    // level deeper, for the resolver and the interpreter, which recurse into 
+    // its left operand. Reports an error at the operator if that is beyond 
+    // the limit.
+    fn chained(&mut self) -> Result<(), LoxError> {
+        if self.nesting >= self.max_nesting {
+            return Err(error(self.previous(), "Too much nesting."));
+        }
+
+        self.nesting += 1;
+        Ok(())
+    }
+
     fn peek(&self) -> &Token {
         &self.tokens[self.current]
     }
@@ -78,9 +125,15 @@ impl<'a> Parser<'a> {
         }
     }
 
+    // Rust-specific. Not in the book. The span from `start` up to the end 
+    // of the token just consumed.
+    fn span_from(&self, start: &Span) -> Span {
+        start.to(self.previous().span())
+    }
+
     // Rust-specific. Not in the book.
     fn literal_expr(&self, value: LiteralValue) -> Expr {
-        Expr::Literal(Literal::new(value))
+        Expr::Literal(Literal::new(value, *self.previous().span()))
     }    
 
     fn primary(&mut self) -> Result<Rc<Expr>, LoxError> {
@@ -112,31 +165,90 @@ impl<'a> Parser<'a> {
             let keyword: Token = self.previous().clone();
             self.consume(&TokenType::Dot, "Expect '.' after 'super'.")?;
             let method: Token = self.consume(&TokenType::Identifier, "Expect superclass method name.")?.clone();
-            return Ok(Rc::new(Expr::Super(Super::new(keyword, method))))
+            let span = self.span_from(keyword.span());
+            return Ok(Rc::new(Expr::Super(Super::new(keyword, method, span))))
         }
 
         if self.match_token(&[TokenType::This]) {
-            return Ok(Rc::new(Expr::This(This::new(self.previous().clone()))))
+            let keyword = self.previous().clone();
+            let span = *keyword.span();
+            return Ok(Rc::new(Expr::This(This::new(keyword, span))))
         }        
 
         if self.match_token(&[TokenType::Identifier]) {
-            return Ok(Rc::new(Expr::Variable(Variable::new(self.previous().clone()))))
+            let name = self.previous().clone();
+            let span = *name.span();
+            return Ok(Rc::new(Expr::Variable(Variable::new(name, span))))
         }
         
         if self.match_token(&[TokenType::LeftParen]) {
+            let start = *self.previous().span();
             let expr = self.expression()?;
             self.consume(&TokenType::RightParen, "Expect ')' after expression.")?;
-            return Ok(Rc::new(Expr::Grouping(Grouping::new(expr))));
+            return Ok(Rc::new(Expr::Grouping(Grouping::new(expr, self.span_from(&start)))));
+        }
+
+        if self.match_token(&[TokenType::LeftBracket]) {
+            return self.list();
+        }
+
+        if self.match_token(&[TokenType::LeftBrace]) {
+            return self.map();
         }
 
         Err(error(self.peek(), "Expect expression."))
     }
 
+    // Not in the book: the list literal `[a, b, c]`.
+    fn list(&mut self) -> Result<Rc<Expr>, LoxError> {
+        let start = *self.previous().span();
+        let mut elements: Vec<Rc<Expr>> = vec![];
+
+        if !self.check(&TokenType::RightBracket) {
+            loop {
+                elements.push(self.expression()?);
+
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(&TokenType::RightBracket, "Expect ']' after list elements.")?;
+
+        Ok(Rc::new(Expr::List(List::new(elements, self.span_from(&start)))))
+    }
+
+    // Not in the book: the map literal `{ "k": v }`. A `{` which starts a 
+    // statement is a block, so a map literal never begins a statement.
+    fn map(&mut self) -> Result<Rc<Expr>, LoxError> {
+        let start = *self.previous().span();
+        let mut keys: Vec<Rc<Expr>> = vec![];
+        let mut values: Vec<Rc<Expr>> = vec![];
+
+        if !self.check(&TokenType::RightBrace) {
+            loop {
+                keys.push(self.expression()?);
+                self.consume(&TokenType::Colon, "Expect ':' after map key.")?;
+                values.push(self.expression()?);
+
+                if !self.match_token(&[TokenType::Comma]) {
+                    break;
+                }
+            }
+        }
+
+        let brace: Token = self.consume(&TokenType::RightBrace, "Expect '}' after map entries.")?.clone();
+
+        Ok(Rc::new(Expr::Map(Map::new(brace, keys, values, self.span_from(&start)))))
+    }
+
     fn unary(&mut self) -> Result<Rc<Expr>, LoxError> {
         if self.match_token(&[TokenType::Bang, TokenType::Minus]) {
             let operator = self.previous().clone();            
-            let right = self.unary()?;
-            Ok(Rc::new(Expr::Unary(Unary::new(operator, right))))
+            let right = self.nested(Self::unary)?;
+            let span = operator.span().to(right.span());
+            Ok(Rc::new(Expr::Unary(Unary::new(operator, right, span))))
         } else {
             self.call()
         }
@@ -160,7 +272,8 @@ impl<'a> Parser<'a> {
 
         let paren: Token = self.consume(&TokenType::RightParen, "Expect ')' after arguments.")?.clone();
 
-        Ok(Rc::new(Expr::Call(Call::new(callee, paren, arguments))))
+        let span = self.span_from(callee.span());
+        Ok(Rc::new(Expr::Call(Call::new(callee, paren, arguments, span))))
     }
 
     fn call(&mut self) -> Result<Rc<Expr>, LoxError> {
@@ -168,10 +281,19 @@ impl<'a> Parser<'a> {
 
         loop {
             if self.match_token(&[TokenType::LeftParen]) {
+                self.chained()?;
                 expr = self.finish_call(expr)?;
             } else if self.match_token(&[TokenType::Dot]) {
+                self.chained()?;
                 let name = self.consume(&TokenType::Identifier, "Expect property name after '.'.")?.clone();
-                expr = Rc::new(Expr::Get(Get::new(expr, name)));
+                let span = self.span_from(expr.span());
+                expr = Rc::new(Expr::Get(Get::new(expr, name, span)));
+            } else if self.match_token(&[TokenType::LeftBracket]) {
+                self.chained()?;
+                let index = self.expression()?;
+                let bracket = self.consume(&TokenType::RightBracket, "Expect ']' after index.")?.clone();
+                let span = self.span_from(expr.span());
+                expr = Rc::new(Expr::Index(Index::new(expr, bracket, index, span)));
             } else {
                 break;
             }
@@ -185,8 +307,10 @@ impl<'a> Parser<'a> {
 
         while self.match_token(&[TokenType::Slash, TokenType::Star]) {
             let operator = self.previous().clone();
+            self.chained()?;
             let right = self.unary()?;
-            expr = Rc::new(Expr::Binary(Binary::new(expr, operator, right)));
+            let span = expr.span().to(right.span());
+            expr = Rc::new(Expr::Binary(Binary::new(expr, operator, right, span)));
         }
 
         Ok(expr)
@@ -197,8 +321,10 @@ impl<'a> Parser<'a> {
 
         while self.match_token(&[TokenType::Minus, TokenType::Plus]) {
             let operator = self.previous().clone();
+            self.chained()?;
             let right = self.factor()?;
-            expr = Rc::new(Expr::Binary(Binary::new(expr, operator, right)));
+            let span = expr.span().to(right.span());
+            expr = Rc::new(Expr::Binary(Binary::new(expr, operator, right, span)));
         }
 
         Ok(expr)
@@ -211,8 +337,10 @@ impl<'a> Parser<'a> {
                 TokenType::Greater, TokenType::GreaterEqual, 
                 TokenType::Less, TokenType::LessEqual]) {
             let operator = self.previous().clone();
+            self.chained()?;
             let right = self.term()?;
-            expr = Rc::new(Expr::Binary(Binary::new(expr, operator, right)));
+            let span = expr.span().to(right.span());
+            expr = Rc::new(Expr::Binary(Binary::new(expr, operator, right, span)));
         }
 
         Ok(expr)        
@@ -223,8 +351,10 @@ impl<'a> Parser<'a> {
 
         while self.match_token(&[TokenType::BangEqual, TokenType::EqualEqual]) {
             let operator = self.previous().clone();
+            self.chained()?;
             let right = self.comparison()?;
-            expr = Rc::new(Expr::Binary(Binary::new(expr, operator, right)));
+            let span = expr.span().to(right.span());
+            expr = Rc::new(Expr::Binary(Binary::new(expr, operator, right, span)));
         }
 
         Ok(expr)
@@ -235,8 +365,10 @@ impl<'a> Parser<'a> {
 
         while self.match_token(&[TokenType::And]) {
             let operator = self.previous().clone();
+            self.chained()?;
             let right = self.equality()?;
-            expr = Rc::new(Expr::Logical(Logical::new(expr, operator, right)));
+            let span = expr.span().to(right.span());
+            expr = Rc::new(Expr::Logical(Logical::new(expr, operator, right, span)));
         }
 
         Ok(expr)
@@ -247,15 +379,17 @@ impl<'a> Parser<'a> {
 
         while self.match_token(&[TokenType::Or]) {
             let operator = self.previous().clone();
+            self.chained()?;
             let right = self.and()?;
-            expr = Rc::new(Expr::Logical(Logical::new(expr, operator, right)));
+            let span = expr.span().to(right.span());
+            expr = Rc::new(Expr::Logical(Logical::new(expr, operator, right, span)));
         }
 
         Ok(expr)
     }
 
     fn expression(&mut self) -> Result<Rc<Expr>, LoxError> {
-        self.assignment()
+        self.nested(Self::assignment)
     }
 
     fn declaration(&mut self) -> Result<Rc<Stmt>, LoxError> {
@@ -263,6 +397,8 @@ impl<'a> Parser<'a> {
             self.class_declaration()
         } else if self.match_token(&[TokenType::Fun]) {
             self.function("function")
+        } else if self.match_token(&[TokenType::Import]) {
+            self.import_declaration()
         } else if self.match_token(&[TokenType::Var]) {
             self.var_declaration()
         } else {
@@ -289,7 +425,10 @@ impl<'a> Parser<'a> {
             match self.peek().token_type() {
                 TokenType::Class | TokenType::Fun | TokenType::Var |
                 TokenType::For | TokenType::If | TokenType::While |
-                TokenType::Print | TokenType::Return => { return; }
+                TokenType::Import |
+                TokenType::Print | TokenType::Return |
+                TokenType::Break | TokenType::Continue |
+                TokenType::Throw | TokenType::Try => { return; }
                 _ => (),
             }
 
@@ -297,27 +436,125 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn break_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let keyword: Token = self.previous().clone();
+        self.consume(&TokenType::Semicolon, "Expect ';' after 'break'.")?;
+
+        let span = self.span_from(keyword.span());
+        Ok(Rc::new(stmt::Stmt::Break(stmt::Break::new(keyword, span))))
+    }
+
+    fn continue_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let keyword: Token = self.previous().clone();
+        self.consume(&TokenType::Semicolon, "Expect ';' after 'continue'.")?;
+
+        let span = self.span_from(keyword.span());
+        Ok(Rc::new(stmt::Stmt::Continue(stmt::Continue::new(keyword, span))))
+    }
+
+    // `as` is not a keyword: it is only special straight after the path.
+    fn import_declaration(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let keyword: Token = self.previous().clone();
+        let path_token: Token = self.consume(&TokenType::String, "Expect module path after 'import'.")?.clone();
+        let path = match path_token.literal() {
+            Some(LiteralValue::String(s)) => s.clone(),
+            _ => unreachable!("String token without a string literal"),
+        };
+
+        let name: Token = if self.check(&TokenType::Identifier) && self.peek().lexeme() == "as" {
+            self.advance();
+            self.consume(&TokenType::Identifier, "Expect module name after 'as'.")?.clone()
+        } else {
+            // Without `as`, the module is named after its file.
+            let stem = std::path::Path::new(&path)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("");
+            if !Self::is_identifier(stem) {
+                return Err(error(&path_token, 
+                    &format!("Can't name module '{}', use 'as' to name it.", path)));
+            }
+            Token::new(TokenType::Identifier, stem.to_string(), None, path_token.line())
+                .with_span(*path_token.span())
+        };
+
+        self.consume(&TokenType::Semicolon, "Expect ';' after import.")?;
+        let span = self.span_from(keyword.span());
+        Ok(Rc::new(stmt::Stmt::Import(stmt::Import::new(keyword, path, name, span))))
+    }
+
+    fn is_identifier(name: &str) -> bool {
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => 
+                chars.all(|c| c.is_ascii_alphanumeric() || c == '_') &&
+                    !Scanner::create_keywords_map().contains_key(name),
+            _ => false,
+        }
+    }
+
+    fn throw_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let keyword: Token = self.previous().clone();
+        let start = *keyword.span();
+        let value: Rc<Expr> = self.expression()?;
+        self.consume(&TokenType::Semicolon, "Expect ';' after thrown value.")?;
+
+        Ok(Rc::new(stmt::Stmt::Throw(stmt::Throw::new(keyword, value, self.span_from(&start)))))
+    }
+
+    fn try_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let start = *self.previous().span();
+        self.consume(&TokenType::LeftBrace, "Expect '{' after 'try'.")?;
+        let body: Vec<Rc<Stmt>> = self.block()?;
+
+        let mut catch_name: Option<Token> = None;
+        let mut catch_body: Option<Vec<Rc<Stmt>>> = None;
+        if self.match_token(&[TokenType::Catch]) {
+            self.consume(&TokenType::LeftParen, "Expect '(' after 'catch'.")?;
+            catch_name = Some(self.consume(&TokenType::Identifier, "Expect exception variable name.")?.clone());
+            self.consume(&TokenType::RightParen, "Expect ')' after exception variable name.")?;
+            self.consume(&TokenType::LeftBrace, "Expect '{' before catch body.")?;
+            catch_body = Some(self.block()?);
+        }
+
+        let mut finally_body: Option<Vec<Rc<Stmt>>> = None;
+        if self.match_token(&[TokenType::Finally]) {
+            self.consume(&TokenType::LeftBrace, "Expect '{' after 'finally'.")?;
+            finally_body = Some(self.block()?);
+        }
+
+        if catch_body.is_none() && finally_body.is_none() {
+            return Err(error(self.peek(), "Expect 'catch' or 'finally' after try block."));
+        }
+
+        let span = self.span_from(&start);
+        Ok(Rc::new(stmt::Stmt::Try(stmt::Try::new(body, catch_name, catch_body, finally_body, span))))
+    }
+
     fn if_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let start = *self.previous().span();
         self.consume(&TokenType::LeftParen, "Expect '(' after 'if'.")?;
         let condition: Rc<Expr> = self.expression()?;
         self.consume(&TokenType::RightParen, "Expect ')' after if condition.")?;
 
-        let then_branch: Rc<Stmt> = self.statement()?;
+        let then_branch: Rc<Stmt> = self.nested(Self::statement)?;
 
         let else_branch: Option<Rc<Stmt>> = if self.match_token(&[TokenType::Else]) {
-            Some(self.statement()?)
+            Some(self.nested(Self::statement)?)
         } else {
             None
         };
 
-        Ok(Rc::new(stmt::Stmt::If(stmt::If::new(condition, then_branch, else_branch))))
+        let span = self.span_from(&start);
+        Ok(Rc::new(stmt::Stmt::If(stmt::If::new(condition, then_branch, else_branch, span))))
     }
 
     fn print_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let start = *self.previous().span();
         let value: Rc<Expr> = self.expression()?;
         self.consume(&TokenType::Semicolon, "Expect ';' after value.")?;
 
-        Ok(Rc::new(stmt::Stmt::Print(stmt::Print::new(value))))
+        Ok(Rc::new(stmt::Stmt::Print(stmt::Print::new(value, self.span_from(&start)))))
     }
 
     fn return_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
@@ -328,12 +565,26 @@ impl<'a> Parser<'a> {
         } else { None };
 
         self.consume(&TokenType::Semicolon, "Expect ';' after return value.")?;
-        Ok(Rc::new(stmt::Stmt::Return(stmt::Return::new(keyword, value))))
+        let span = self.span_from(keyword.span());
+        Ok(Rc::new(stmt::Stmt::Return(stmt::Return::new(keyword, value, span))))
     }
 
-    fn var_declaration(&mut self) -> Result<Rc<Stmt>, LoxError> {
+    // Not in the book: the name of a declaration, with the doc comment of its 
+    // `fun`, `class` or `var` keyword. A method has no keyword: the scanner 
+    // attaches its doc comment to the name itself.
+    fn declaration_name(&mut self, message: &str) -> Result<Token, LoxError> {
+        let doc = self.previous().doc().map(str::to_string);
         // Clone the token immediately to break the borrow
-        let name = self.consume(&TokenType::Identifier, "Expect variable name.")?.clone();
+        let name = self.consume(&TokenType::Identifier, message)?.clone();
+        Ok(match doc {
+            Some(doc) => name.with_doc(doc),
+            None => name,
+        })
+    }
+
+    fn var_declaration(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let start = *self.previous().span();
+        let name = self.declaration_name("Expect variable name.")?;
 
         let mut initializer = None;
         if self.match_token(&[TokenType::Equal]) {
@@ -342,19 +593,22 @@ impl<'a> Parser<'a> {
 
         self.consume(&TokenType::Semicolon, "Expect ';' after variable declaration.")?;
 
-        Ok(Rc::new(stmt::Stmt::Var(stmt::Var::new(name, initializer))))
+        Ok(Rc::new(stmt::Stmt::Var(stmt::Var::new(name, initializer, self.span_from(&start)))))
     }
 
     fn while_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let start = *self.previous().span();
         self.consume(&TokenType::LeftParen, "Expect '(' after 'while'.")?;
         let condition: Rc<Expr> = self.expression()?;
         self.consume(&TokenType::RightParen, "Expect ')' after condition.")?;
-        let body: Rc<Stmt> = self.statement()?;
+        let body: Rc<Stmt> = self.nested(Self::statement)?;
 
-        Ok(Rc::new(stmt::Stmt::While(stmt::While::new(condition, body))))
+        let span = self.span_from(&start);
+        Ok(Rc::new(stmt::Stmt::While(stmt::While::new(condition, body, None, span))))
     }
 
     fn for_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
+        let start = *self.previous().span();
         // The opening parenthesis before the clauses.
         self.consume(&TokenType::LeftParen, "Expect '(' after 'for'.")?;
 
@@ -365,52 +619,68 @@ impl<'a> Parser<'a> {
         } else if self.match_token(&[TokenType::Var]) {
             initializer = Some(self.var_declaration()?);
         } else {
+            self.no_map_literal()?;
             initializer = Some(self.expression_statement()?);
         };
 
         // Loop condition clause.
         let condition: Option<Rc<Expr>> = if !self.check(&TokenType::Semicolon) {
+            self.no_map_literal()?;
             Some(self.expression()?) } else { None };
         self.consume(&TokenType::Semicolon, "Expect ';' after loop condition.")?;
 
         // Increment clause.    
         let increment: Option<Rc<Expr>> = if !self.check(&TokenType::RightParen) {
+            self.no_map_literal()?;
             Some(self.expression()?) } else { None };
         self.consume(&TokenType::RightParen, "Expect ')' after for clauses.")?;
 
-        let mut body = self.statement()?;
-
-        if let Some(inc) = increment {
-            let new_body: Vec<Rc<Stmt>> = vec![
-                body,
-                Rc::new(stmt::Stmt::Expression(stmt::Expression::new(inc)))
-            ];
-            body = Rc::new(stmt::Stmt::Block(stmt::Block::new(new_body)));
-        };
+        let mut body = self.nested(Self::statement)?;
+        // The desugared nodes all get the span of the whole `for` statement.
+        let span = self.span_from(&start);
 
+        // The book appends the increment to the end of the body in a new block. 
+        // A `continue` would then skip it, so we hand the increment to the 
+        // `While` node instead: the interpreter runs it after every iteration 
+        // of the body, including ones cut short by `continue`.
         body = Rc::new(stmt::Stmt::While(stmt::While::new(
             condition.unwrap_or(
-                Rc::new(Expr::Literal(Literal::new(LiteralValue::Boolean(true))))
+                Rc::new(Expr::Literal(Literal::new(LiteralValue::Boolean(true), span)))
             ), 
-            body)
+            body,
+            increment,
+            span)
         ));
 
         if let Some(ini) = initializer {
-            body = Rc::new(stmt::Stmt::Block(stmt::Block::new(vec![ini, body])));
+            body = Rc::new(stmt::Stmt::Block(stmt::Block::new(vec![ini, body], span)));
         };
 
         Ok(body)
     }
 
+    // Not in the book: a `{` which starts a `for` clause is an error, as it is 
+    // in the book, rather than a map literal: like a `{` which starts a 
+    // statement, it reads as a block.
+    fn no_map_literal(&self) -> Result<(), LoxError> {
+        if self.check(&TokenType::LeftBrace) {
+            return Err(error(self.peek(), "Expect expression."));
+        }
+        Ok(())
+    }
+
     fn expression_statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
         let expr: Rc<Expr> = self.expression()?;
         self.consume(&TokenType::Semicolon, "Expect ';' after expression.")?;
 
-        Ok(Rc::new(stmt::Stmt::Expression(stmt::Expression::new(expr))))
+        let span = self.span_from(expr.span());
+        Ok(Rc::new(stmt::Stmt::Expression(stmt::Expression::new(expr, span))))
     }
 
     fn function(&mut self, kind: &str) -> Result<Rc<Stmt>, LoxError> {
-        let name: Token = self.consume(&TokenType::Identifier, &format!("Expect {} name.", kind))?.clone();
+        // A function declaration starts at `fun`, a method at its name.
+        let start = if kind == "method" { *self.peek().span() } else { *self.previous().span() };
+        let name: Token = self.declaration_name(&format!("Expect {} name.", kind))?;
 
         // Parse the parameter list and the pair of parentheses wrapped around it.
         self.consume(&TokenType::LeftParen, &format!("Expect '(' after {} name.", kind))?;
@@ -435,16 +705,20 @@ impl<'a> Parser<'a> {
         // Parse the body and wrap it all up in a function node.
         self.consume(&TokenType::LeftBrace, &format!("Expect '{{' before {} body.", kind))?;
         let body: Vec<Rc<Stmt>> = self.block()?;
-        Ok(Rc::new(Stmt::Function(Function::new(name, parameters, body))))
+        let span = self.span_from(&start);
+        Ok(Rc::new(Stmt::Function(Function::new(name, parameters, body, span))))
     }
 
     fn class_declaration(&mut self) -> Result<Rc<Stmt>, LoxError> {
-        let name: Token = self.consume(&TokenType::Identifier, "Expect class name.")?.clone();
+        let start = *self.previous().span();
+        let name: Token = self.declaration_name("Expect class name.")?;
 
         let mut superclass: Option<Rc<Expr>> = None;
         if self.match_token(&[TokenType::Less]) {
             self.consume(&TokenType::Identifier, "Expect superclass name.")?;
-            superclass = Some(Rc::new(Expr::Variable(Variable::new(self.previous().clone()))));
+            let name = self.previous().clone();
+            let span = *name.span();
+            superclass = Some(Rc::new(Expr::Variable(Variable::new(name, span))));
         }
 
         self.consume(&TokenType::LeftBrace, "Expect '{' before class body.")?;
@@ -459,7 +733,8 @@ impl<'a> Parser<'a> {
 
         self.consume(&TokenType::RightBrace, "Expect '}' after class body.")?;
 
-        Ok(Rc::new(Stmt::Class(Class::new(name, superclass, methods))))
+        let span = self.span_from(&start);
+        Ok(Rc::new(Stmt::Class(Class::new(name, superclass, methods, span))))
     }
 
     // The author's note:
@@ -475,7 +750,7 @@ impl<'a> Parser<'a> {
         let mut statements: Vec<Rc<Stmt>> = Vec::new();
 
         while !self.check(&TokenType::RightBrace) && !self.is_at_end() {
-            statements.push(self.declaration()?);
+            statements.push(self.nested(Self::declaration)?);
         }
 
         self.consume(&TokenType::RightBrace, "Expect '}' after block.")?;
@@ -487,16 +762,23 @@ impl<'a> Parser<'a> {
 
         if self.match_token(&[TokenType::Equal]) {
             let equals: Token = self.previous().clone();
-            let value: Rc<Expr> = self.assignment()?;
+            let value: Rc<Expr> = self.nested(Self::assignment)?;
+            let span = expr.span().to(value.span());
 
             match expr.as_ref() {
                 Expr::Variable(var) => 
-                    return Ok(Rc::new(Expr::Assign(Assign::new(var.name().clone(), value)))),
+                    return Ok(Rc::new(Expr::Assign(Assign::new(var.name().clone(), value, span)))),
                 Expr::Get(get) =>
                     return Ok(Rc::new(Expr::Set(Set::new(
-                        Rc::clone(get.object()), get.name().clone(), value)))
+                        Rc::clone(get.object()), get.name().clone(), value, span)))
+                    ),
+                Expr::Index(index) =>
+                    return Ok(Rc::new(Expr::IndexSet(IndexSet::new(
+                        Rc::clone(index.object()), index.bracket().clone(), 
+                        Rc::clone(index.index()), value, span)))
                     ),
-                _ => return Err(error(&equals, "Invalid assignment target."))
+                _ => return Err(error(&equals, "Invalid assignment target.")
+                    .with_help("only variables, properties and list or map elements can be assigned to."))
             }
         }
 
@@ -504,7 +786,11 @@ impl<'a> Parser<'a> {
     }
 
     fn statement(&mut self) -> Result<Rc<Stmt>, LoxError> {
-        if self.match_token(&[TokenType::For]) {
+        if self.match_token(&[TokenType::Break]) {
+            self.break_statement()
+        } else if self.match_token(&[TokenType::Continue]) {
+            self.continue_statement()
+        } else if self.match_token(&[TokenType::For]) {
             self.for_statement()
         } else if self.match_token(&[TokenType::If]) {
             self.if_statement()
@@ -512,10 +798,16 @@ impl<'a> Parser<'a> {
             self.print_statement()
         } else if self.match_token(&[TokenType::Return]) {
             self.return_statement()
+        } else if self.match_token(&[TokenType::Throw]) {
+            self.throw_statement()
+        } else if self.match_token(&[TokenType::Try]) {
+            self.try_statement()
         } else if self.match_token(&[TokenType::While]) {
             self.while_statement()
         } else if self.match_token(&[TokenType::LeftBrace]) {
-            Ok(Rc::new(stmt::Stmt::Block(stmt::Block::new(self.block()?))))
+            let start = *self.previous().span();
+            let statements = self.block()?;
+            Ok(Rc::new(stmt::Stmt::Block(stmt::Block::new(statements, self.span_from(&start)))))
         } else {
             self.expression_statement()
         }
@@ -532,19 +824,19 @@ impl<'a> Parser<'a> {
     // newline ( \n ) character.
     pub fn parse(&mut self) -> Result<Vec<Rc<Stmt>>, LoxError> {
         let mut statements: Vec<Rc<Stmt>> = vec![];
-        let mut err_msgs: Vec<std::string::String> = vec![];
+        let mut errors: Vec<LoxError> = vec![];
 
         while !self.is_at_end() {
             match self.declaration() {
                 Ok(stmt) => statements.push(stmt),
-                Err(err) => err_msgs.push(format!("{}", err)),
+                Err(err) => errors.push(err),
             }
         }
         
-        if err_msgs.len() == 0 {
+        if errors.is_empty() {
             Ok(statements)
         } else {
-            Err(sys_error("", &err_msgs.join("\n")))
+            Err(LoxError::from_errors(errors))
         }
     }
 }
@@ -558,7 +850,7 @@ mod tests {
     #[test]
     fn test_rc_identity_for_variable_expr() {
         let token = Token::new(TokenType::Identifier, "x".to_string(), None, 1);
-        let expr1 = Rc::new(Expr::Variable(Variable::new(token.clone())));
+        let expr1 = Rc::new(Expr::Variable(Variable::new(token.clone(), *token.span())));
         let expr2 = Rc::clone(&expr1);
 
         assert!(Rc::ptr_eq(&expr1, &expr2));
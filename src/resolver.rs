@@ -9,7 +9,8 @@ use std::collections::HashMap;
 
 use super::interpreter::Interpreter;
 use super::lox_runtime_error::LoxRuntimeError;
-use super::lox_error_helper::{sys_error, runtime_error};
+use super::lox_error::LoxError;
+use super::lox_error_helper::runtime_error;
 use super::{expr, expr::Expr};
 use super::{stmt, stmt::{Stmt, Function}};
 use super::{unwrap_expr, unwrap_stmt};
@@ -35,6 +36,9 @@ pub struct Resolver<'a> {
     scopes: Vec<HashMap<String, bool>>,
     current_function: FunctionType,
     current_class: ClassType,
+    // Number of enclosing loops in the current function body. `break` and 
+    // `continue` are only valid when this is greater than zero.
+    loop_depth: usize,
 }
 
 impl<'a> Resolver<'a> {
@@ -44,6 +48,7 @@ impl<'a> Resolver<'a> {
             scopes: Vec::new(),
             current_function: FunctionType::Nil,
             current_class: ClassType::None,
+            loop_depth: 0,
         }
     }
 
@@ -59,18 +64,18 @@ impl<'a> Resolver<'a> {
     // When there are multiple errors, they are separated by a 
     // newline ( \n ) character.
     pub fn resolve(&mut self, statements: &Vec<Rc<Stmt>>) -> Result<(), LoxRuntimeError> {
-        let mut err_msgs: Vec<String> = vec![];
+        let mut errors: Vec<LoxError> = vec![];
 
         for stmt in statements {
             match self.resolve_statement(Rc::clone(stmt)) {
                 Ok(_) => {},
-                Err(err) => err_msgs.push(format!("{}", err)),
+                Err(err) => errors.push(err.into()),
             }            
         }
-        if err_msgs.len() == 0 {
+        if errors.is_empty() {
             Ok(())
         } else {
-            Err(LoxRuntimeError::Error(sys_error("", &err_msgs.join("\n"))))
+            Err(LoxRuntimeError::Error(LoxError::from_errors(errors)))
         }
     }
 
@@ -117,6 +122,11 @@ impl<'a> Resolver<'a> {
         let enclosing_function: FunctionType = self.current_function;
         self.current_function = func_type;
 
+        // A function body starts outside of any loop, even when the function 
+        // itself is declared inside one.
+        let enclosing_loop_depth = self.loop_depth;
+        self.loop_depth = 0;
+
         self.begin_scope();
         for param in function.params() {
             self.declare(param)?;
@@ -127,6 +137,7 @@ impl<'a> Resolver<'a> {
         self.end_scope();
         
         self.current_function = enclosing_function;
+        self.loop_depth = enclosing_loop_depth;
         res
     }
 }
@@ -172,6 +183,35 @@ impl<'a> expr::Visitor<()> for Resolver<'a> {
         Ok(())
     }
 
+    fn visit_index_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let index = unwrap_expr!(expr, Index);
+
+        self.resolve_expression(Rc::clone(index.object()))?;
+        self.resolve_expression(Rc::clone(index.index()))?;
+
+        Ok(())
+    }
+
+    fn visit_indexset_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let index_set = unwrap_expr!(expr, IndexSet);
+
+        self.resolve_expression(Rc::clone(index_set.value()))?;
+        self.resolve_expression(Rc::clone(index_set.object()))?;
+        self.resolve_expression(Rc::clone(index_set.index()))?;
+
+        Ok(())
+    }
+
+    fn visit_list_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let list = unwrap_expr!(expr, List);
+
+        for element in list.elements() {
+            self.resolve_expression(Rc::clone(element))?;
+        }
+
+        Ok(())
+    }
+
     fn visit_literal_expr(&mut self, _: Rc<Expr>) -> Result<(), LoxRuntimeError> {
         Ok(())
     }
@@ -185,6 +225,17 @@ impl<'a> expr::Visitor<()> for Resolver<'a> {
         Ok(())
     }
 
+    fn visit_map_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let map = unwrap_expr!(expr, Map);
+
+        for (key, value) in map.keys().iter().zip(map.values()) {
+            self.resolve_expression(Rc::clone(key))?;
+            self.resolve_expression(Rc::clone(value))?;
+        }
+
+        Ok(())
+    }
+
     fn visit_set_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
         let set = unwrap_expr!(expr, Set);
 
@@ -264,6 +315,16 @@ impl<'a> stmt::Visitor<()> for Resolver<'a> {
         res
     }
 
+    fn visit_break_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Break);
+
+        if self.loop_depth == 0 {
+            return Err(runtime_error(inner.keyword(), "Can't use 'break' outside of a loop."));
+        }
+
+        Ok(())
+    }
+
     fn visit_class_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let class = unwrap_stmt!(stmt, Class);
 
@@ -326,6 +387,16 @@ impl<'a> stmt::Visitor<()> for Resolver<'a> {
         Ok(())
     }
 
+    fn visit_continue_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Continue);
+
+        if self.loop_depth == 0 {
+            return Err(runtime_error(inner.keyword(), "Can't use 'continue' outside of a loop."));
+        }
+
+        Ok(())
+    }
+
     fn visit_expression_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let expression = unwrap_stmt!(stmt, Expression);
         self.resolve_expression(Rc::clone(expression.expression()))
@@ -361,6 +432,15 @@ impl<'a> stmt::Visitor<()> for Resolver<'a> {
         Ok(())
     }
 
+    // The imported module is resolved on its own when it is first run.
+    fn visit_import_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let import = unwrap_stmt!(stmt, Import);
+
+        self.declare(import.name())?;
+        self.define(import.name());
+        Ok(())
+    }
+
     fn visit_return_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let inner = unwrap_stmt!(stmt, Return);
 
@@ -379,6 +459,39 @@ impl<'a> stmt::Visitor<()> for Resolver<'a> {
         Ok(())
     }
 
+    fn visit_throw_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Throw);
+
+        self.resolve_expression(Rc::clone(inner.value()))
+    }
+
+    fn visit_try_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Try);
+
+        self.begin_scope();
+        let res = self.resolve(inner.body());
+        self.end_scope();
+        res?;
+
+        // The exception variable lives in the same scope as the catch body.
+        if let (Some(name), Some(catch_body)) = (inner.catch_name(), inner.catch_body()) {
+            self.begin_scope();
+            let res = self.declare(name)
+                .and_then(|_| { self.define(name); self.resolve(catch_body) });
+            self.end_scope();
+            res?;
+        }
+
+        if let Some(finally_body) = inner.finally_body() {
+            self.begin_scope();
+            let res = self.resolve(finally_body);
+            self.end_scope();
+            res?;
+        }
+
+        Ok(())
+    }
+
     fn visit_var_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
         let var = unwrap_stmt!(stmt, Var);
 
@@ -395,6 +508,13 @@ impl<'a> stmt::Visitor<()> for Resolver<'a> {
         let inner = unwrap_stmt!(stmt, While);
 
         self.resolve_expression(Rc::clone(inner.condition()))?;
-        self.resolve_statement(Rc::clone(inner.body()))
+        if let Some(increment) = inner.increment() {
+            self.resolve_expression(Rc::clone(increment))?;
+        }
+
+        self.loop_depth += 1;
+        let res = self.resolve_statement(Rc::clone(inner.body()));
+        self.loop_depth -= 1;
+        res
     }
 }
@@ -14,13 +14,21 @@
 //! Where an identifier is not a keyword in the Java language, but a keyword in Rust, 
 //! it is suffixed with an underscore **-**. E.g. ``match`` in Java is ``match_char`` in
 //! Rust.
+//! 
+//! Not in the book: ``/* ... */`` block comments, which nest, and ``///`` doc 
+//! comments. A doc comment is trivia: it produces no token of its own, it is 
+//! attached to the ``fun``, ``class`` or ``var`` keyword which follows it, or 
+//! to the identifier, which in a class body names a method. The parser moves 
+//! it from the keyword to the declaration's name, see 
+//! [Token::doc()](`crate::token::Token::doc`). Before any other token, it is 
+//! dropped.
 
 use std::collections::HashMap;
 
 use super::lox_error::LoxError;
 use super::lox_error_helper::{scanner_error, sys_error}; 
 use super::scanner_index::ScannerIndex;
-use super::token::{LiteralValue, Token};
+use super::token::{LiteralValue, Span, Token};
 use super::token_type::TokenType;
 
 type KeywordsMap = HashMap<&'static str, TokenType>;
@@ -28,100 +36,183 @@ type KeywordsMap = HashMap<&'static str, TokenType>;
 pub struct Scanner<'a> {
     source: &'a str,
     indexes: ScannerIndex,
+    // The `///` lines since the last token.
+    doc_lines: Vec<String>,
 }
 
 impl<'a> Scanner<'a> {
     pub fn new(source: &'a str) -> Self {
         Scanner {
-            source: source.trim(),
+            source,
             indexes: ScannerIndex::new(),
+            doc_lines: vec![],
         }
     }
 
     fn advance(&mut self) -> Option<char> {
-        if let Some(c) = self.source.chars().nth(self.indexes.current()) {
-            self.indexes.inc_lexeme_indexes(c.len_utf8());
-            Some(c)
-        } else {
-            None
-        }
+        let c = self.source[self.indexes.current()..].chars().next()?;
+        self.indexes.inc_lexeme_indexes(c.len_utf8());
+        Some(c)
     }
 
-    fn is_at_end(&mut self) -> bool {
-        self.indexes.current() >= self.source.chars().count()
+    fn is_at_end(&self) -> bool {
+        self.indexes.current() >= self.source.len()
     }    
 
     fn match_char(&mut self, expected: char) -> bool {
-        if self.is_at_end() {
+        if self.is_at_end() || self.peek() != expected {
             return false;
         }
 
-        match self.source.chars().nth(self.indexes.current()) {
-            Some(c) => {
-                if c != expected {
-                    return false;
-                }
-            }
-            // Not in original https://craftinginterpreters.com/scanning.html#the-scanner-class
-            None => return false
-        }
-
         self.indexes.inc_lexeme_indexes(expected.len_utf8());
         true
     }
 
-    fn peek(&mut self) -> char {
-        if self.is_at_end() {
-            return '\0';
-        }
-
-        if let Some(c) = self.source.chars().nth(self.indexes.current()) {
-            c
-        } else {
-            // Not in original https://craftinginterpreters.com/scanning.html#the-scanner-class
-            '\0'
-        }
+    // Not in https://craftinginterpreters.com/scanning.html#the-scanner-class: 
+    // the remaining source, `current` is always on a character boundary.
+    fn rest(&self) -> &'a str {
+        &self.source[self.indexes.current()..]
     }
 
-    fn peek_next(&mut self) -> char {
-        if (self.indexes.current() + 1) >= self.source.len() {
-            return '\0';
-        }
+    fn peek(&self) -> char {
+        self.rest().chars().next().unwrap_or('\0')
+    }
 
-        if let Some(c) = self.source.chars().nth(self.indexes.current() + 1) {
-            c
-        } else {
-            // Not in https://craftinginterpreters.com/scanning.html#the-scanner-class
-            '\0'
-        }
+    fn peek_next(&self) -> char {
+        self.rest().chars().nth(1).unwrap_or('\0')
     }
 
     fn string(&mut self, lst: &mut Vec<Token>) -> Result<(), LoxError> {
+        let mut value = String::new();
+        // Not in the book: the first invalid escape sequence. It is reported 
+        // after the closing ", so that scanning carries on after the string.
+        let mut escape_error: Option<LoxError> = None;
 
         while (self.peek() != '"') && !self.is_at_end() {
-            if self.peek() == '\n' {
-                self.indexes.inc_line();
+            let at = self.indexes.here();
+            // Advances first, so that the next line's columns start from 1.
+            match self.advance() {
+                Some('\n') => {
+                    self.indexes.inc_line();
+                    value.push('\n');
+                }
+                Some('\\') => match self.escape() {
+                    Some(c) => value.push(c),
+                    None => {
+                        escape_error.get_or_insert_with(|| self.escape_error(at));
+                    }
+                },
+                Some(c) => value.push(c),
+                None => {},
             }
-
-            self.advance();
         }
 
         if self.is_at_end() {
-            return Err(scanner_error(self.indexes.line(), self.peek(), "Unterminated string."));
+            // At the opening ", rather than at the end of the source.
+            return Err(scanner_error(self.indexes.start_line(), self.peek(), "Unterminated string.")
+                .with_span(self.indexes.span())
+                .with_help("add a closing '\"' to end the string."));
         }
 
         // The closing ".
         self.advance();
 
-        // Trim the surrounding quotes.
-        let value = self.source[self.indexes.start() + 1..self.indexes.byte_count() - 1].to_string();
-
+        // The token is added even after an invalid escape sequence, so that the 
+        // parser does not report errors which follow from a missing string.
         self.add_token_with_literal(lst, TokenType::String, 
             Some(LiteralValue::String(value)));
 
+        match escape_error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    // Not in the book. The \ has been consumed: the escaped character, None 
+    // if the escape sequence is invalid.
+    fn escape(&mut self) -> Option<char> {
+        let c = match self.peek() {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            '\\' => '\\',
+            '"' => '"',
+            'u' => {
+                self.advance();
+                return self.unicode_escape();
+            }
+            // Not consumed: the newline is counted, and the end of the source 
+            // is an unterminated string.
+            _ if self.is_at_end() => return None,
+            '\n' => return None,
+            _ => {
+                self.advance();
+                return None;
+            }
+        };
+        self.advance();
+        Some(c)
+    }
+
+    // Not in the book. \u has been consumed: {, one to six hex digits, and }.
+    fn unicode_escape(&mut self) -> Option<char> {
+        if !self.match_char('{') {
+            return None;
+        }
+
+        let start = self.indexes.current();
+        while self.peek().is_ascii_hexdigit() {
+            self.advance();
+        }
+        let digits = &self.source[start..self.indexes.current()];
+
+        if !self.match_char('}') || digits.is_empty() || digits.len() > 6 {
+            return None;
+        }
+        // Not a surrogate, and not above U+10FFFF.
+        u32::from_str_radix(digits, 16).ok().and_then(char::from_u32)
+    }
+
+    // Not in the book. The escape sequence from `at` up to `current`.
+    fn escape_error(&self, at: Span) -> LoxError {
+        let span = at.to(&self.indexes.here());
+        LoxError::new(span.line, &self.source[span.start..span.end], "Invalid escape sequence.")
+            .with_span(span)
+            .with_help("the escape sequences are \\n, \\t, \\r, \\0, \\\\, \\\" and \\u{XXXX}.")
+    }
+
+    // Not in the book. The opening /* has been consumed.
+    fn block_comment(&mut self) -> Result<(), LoxError> {
+        let mut depth = 1;
+
+        while depth > 0 {
+            match self.advance() {
+                Some('\n') => self.indexes.inc_line(),
+                Some('/') if self.match_char('*') => depth += 1,
+                Some('*') if self.match_char('/') => depth -= 1,
+                Some(_) => {},
+                // At the opening /*, rather than at the end of the source.
+                None => return Err(LoxError::new(self.indexes.start_line(), "/*", "Unterminated block comment.")
+                    .with_span(self.indexes.span())
+                    .with_help("add a closing '*/' to end the comment.")),
+            }
+        }
+
         Ok(())
     }
 
+    // Not in the book. The opening /// has been consumed.
+    fn doc_comment(&mut self) {
+        while (self.peek() != '\n') && !self.is_at_end() {
+            self.advance();
+        }
+
+        let text = &self.source[self.indexes.start() + 3..self.indexes.current()];
+        let text = text.strip_prefix(' ').unwrap_or(text);
+        self.doc_lines.push(text.trim_end().to_string());
+    }
+
     fn is_digit(c: char) -> bool {
         (c >= '0') & (c <= '9')
     }
@@ -141,7 +232,7 @@ impl<'a> Scanner<'a> {
             }
         }
 
-        let mut str = self.source[self.indexes.start()..self.indexes.byte_count()].to_string();
+        let mut str = self.source[self.indexes.start()..self.indexes.current()].to_string();
         if !str.contains('.') {
             str.push_str(".0");
         } else if str.ends_with('.') {
@@ -150,7 +241,8 @@ impl<'a> Scanner<'a> {
 
         let value = str
             .parse::<f64>()
-            .map_err(|e| scanner_error(self.indexes.line(), self.peek(), &format!("Failed to parse float: {}", e)))?;
+            .map_err(|e| scanner_error(self.indexes.line(), self.peek(), &format!("Failed to parse float: {}", e))
+                .with_span(self.indexes.span()))?;
 
         self.add_token_with_literal(lst, TokenType::Number, 
             Some(LiteralValue::Number(value)));
@@ -173,7 +265,7 @@ impl<'a> Scanner<'a> {
             self.advance();
         }
 
-        let keyword = self.source[self.indexes.start()..self.indexes.byte_count()].to_string();
+        let keyword = self.source[self.indexes.start()..self.indexes.current()].to_string();
 
         if let Some(token) = keywords.get(keyword.as_str()) {
             self.add_token(lst, token.clone());
@@ -182,18 +274,26 @@ impl<'a> Scanner<'a> {
         }
     }
 
-    fn add_token(&self, 
+    fn add_token(&mut self, 
         lst: &mut Vec<Token>, 
         type_: TokenType) {
         self.add_token_with_literal(lst, type_, None);
     }
 
-    fn add_token_with_literal(&self, 
+    fn add_token_with_literal(&mut self, 
         lst: &mut Vec<Token>,
         type_: TokenType, 
         literal: Option<LiteralValue>) {
-            let lex = self.source[self.indexes.start()..self.indexes.byte_count()].to_string();
-            lst.push(Token::new(type_, lex, literal, self.indexes.line()));
+            let lex = self.source[self.indexes.start()..self.indexes.current()].to_string();
+            let mut token = Token::new(type_.clone(), lex, literal, self.indexes.line())
+                .with_span(self.indexes.span());
+
+            let doc_lines = std::mem::take(&mut self.doc_lines);
+            if !doc_lines.is_empty() && matches!(type_, 
+                TokenType::Fun | TokenType::Class | TokenType::Var | TokenType::Identifier) {
+                token = token.with_doc(doc_lines.join("\n"));
+            }
+            lst.push(token);
     }
 
     fn scan_token(&mut self, 
@@ -213,6 +313,9 @@ impl<'a> Scanner<'a> {
             ')' => self.add_token(lst, TokenType::RightParen),
             '{' => self.add_token(lst, TokenType::LeftBrace),
             '}' => self.add_token(lst, TokenType::RightBrace),
+            '[' => self.add_token(lst, TokenType::LeftBracket),
+            ']' => self.add_token(lst, TokenType::RightBracket),
+            ':' => self.add_token(lst, TokenType::Colon),
             ',' => self.add_token(lst, TokenType::Comma),
             '.' => self.add_token(lst, TokenType::Dot),
             '-' => self.add_token(lst, TokenType::Minus),
@@ -242,9 +345,17 @@ impl<'a> Scanner<'a> {
 
             '/' => {
                 if self.match_char('/') {
-                    while (self.peek() != '\n') && !self.is_at_end() {
+                    // ////, or more slashes, is not a doc comment.
+                    if self.peek() == '/' && self.peek_next() != '/' {
                         self.advance();
+                        self.doc_comment();
+                    } else {
+                        while (self.peek() != '\n') && !self.is_at_end() {
+                            self.advance();
+                        }
                     }
+                } else if self.match_char('*') {
+                    self.block_comment()?;
                 } else {
                     self.add_token(lst, TokenType::Slash);
                 }
@@ -262,7 +373,8 @@ impl<'a> Scanner<'a> {
                 } else if Self::is_alpha(c) {
                     self.identifier(keywords, lst);
                 } else {
-                    return Err(scanner_error(self.indexes.line(), c,  &format!("Unexpected character: {}.", c)));
+                    return Err(scanner_error(self.indexes.line(), c,  &format!("Unexpected character: {}.", c))
+                        .with_span(self.indexes.span()));
                 }
             }
         }
@@ -275,19 +387,26 @@ impl<'a> Scanner<'a> {
         let mut keywords = HashMap::new();
         
         keywords.insert("and", TokenType::And);
+        keywords.insert("break", TokenType::Break);
+        keywords.insert("catch", TokenType::Catch);
         keywords.insert("class", TokenType::Class);
+        keywords.insert("continue", TokenType::Continue);
         keywords.insert("else", TokenType::Else);
         keywords.insert("false", TokenType::False);
+        keywords.insert("finally", TokenType::Finally);
         keywords.insert("for", TokenType::For);
         keywords.insert("fun", TokenType::Fun);
         keywords.insert("if", TokenType::If);
+        keywords.insert("import", TokenType::Import);
         keywords.insert("nil", TokenType::Nil);
         keywords.insert("or", TokenType::Or);
         keywords.insert("print", TokenType::Print);
         keywords.insert("return", TokenType::Return);
         keywords.insert("super", TokenType::Super);
         keywords.insert("this", TokenType::This);
+        keywords.insert("throw", TokenType::Throw);
         keywords.insert("true", TokenType::True);
+        keywords.insert("try", TokenType::Try);
         keywords.insert("var", TokenType::Var);
         keywords.insert("while", TokenType::While);
         
@@ -298,32 +417,47 @@ impl<'a> Scanner<'a> {
     // When there are multiple errors, they are separated by a 
     // newline ( \n ) character.    
     pub fn scan_tokens(&mut self) -> Result<Vec<Token>, LoxError> {
-        if self.is_at_end() {
-            return Err(sys_error("", "Source text is empty."));
+        match self.scan_tokens_with_errors() {
+            (tokens, None) => Ok(tokens),
+            (_, Some(err)) => Err(err),
         }
+    }
 
-        let keywords: KeywordsMap = Self::create_keywords_map();
-
+    /// Not in the book: the same as `scan_tokens()`, but the tokens are returned 
+    /// even when there are errors, so that the parser can report its own errors 
+    /// in the same run. An unexpected character, or an unterminated string, 
+    /// has no token: scanning carries on after it.
+    pub fn scan_tokens_with_errors(&mut self) -> (Vec<Token>, Option<LoxError>) {
         let mut tokens = Vec::<Token>::new();
 
-        let mut err_msgs: Vec<String> = vec![];
+        let mut errors: Vec<LoxError> = vec![];
+
+        // The source is not trimmed: lines and columns are those of the file.
+        if self.source.trim().is_empty() {
+            errors.push(sys_error("", "Source text is empty."));
+        }
+
+        let keywords: KeywordsMap = Self::create_keywords_map();
 
         while !self.is_at_end() {
             // We are at the beginning of the next lexeme.
-            let _ = &self.indexes.set_start(self.indexes.byte_count());
+            let _ = &self.indexes.set_start(self.indexes.current());
  
             match self.scan_token(&keywords, &mut tokens) {
                 Ok(_) => {},
-                Err(err) => err_msgs.push(format!("{}", err)),                
+                Err(err) => errors.push(err),
             }
         }
 
-        if err_msgs.len() == 0 {
-            tokens.push(Token::new(TokenType::Eof, "".to_string(), None, self.indexes.line()));
-            Ok(tokens)
+        // An empty span at the end of the source.
+        self.indexes.set_start(self.indexes.current());
+        tokens.push(Token::new(TokenType::Eof, "".to_string(), None, self.indexes.line())
+            .with_span(self.indexes.span()));
+
+        if errors.is_empty() {
+            (tokens, None)
         } else {
-            Err(sys_error("", &err_msgs.join("\n")))
+            (tokens, Some(LoxError::from_errors(errors)))
         }
-
     }
 }
\ No newline at end of file
@@ -4,22 +4,30 @@
 //! the **The Scanner Class** in 
 //! [https://craftinginterpreters.com/scanning.html](https://craftinginterpreters.com/scanning.html). 
 //! 
-//! This implementation supports UTF8. The field ``byte_count`` keeps track of the 
-//! accumulative total bytes up to the character at the ``current``th index.
+//! This implementation supports UTF8. ``start`` and ``current`` are byte offsets 
+//! into the source, always on a character boundary, so that the scanner reads the 
+//! next character in constant time.
+//! 
+//! The fields ``column``, ``start_column`` and ``start_line`` track the columns of 
+//! the lexeme, so that each token gets its [`Span`].
 //! 
 //! I have discussed this implementation in detail in this post [Rust: Working with UTF-8 Text](https://behainguyen.wordpress.com/2025/06/09/rust-working-with-utf-8-text/).
 
+use super::token::Span;
+
 /// See the Java class variables ``start``, ``current`` and ``line`` in 
 /// the **The Scanner Class** in 
 /// [https://craftinginterpreters.com/scanning.html](https://craftinginterpreters.com/scanning.html). 
 pub struct ScannerIndex {
+    /// The byte offset of the first character of the lexeme.
     start: usize,
+    /// The byte offset of the character being considered.
     current: usize,
-    /// An extra field, not present in the original Java implementation. This field is to 
-    /// support UTF8. It keeps track of the accumulative total bytes up to the character at 
-    /// the ``current``th index.
-    byte_count: usize,
     line: usize,
+    /// Characters consumed on the current line.
+    column: usize,
+    start_column: usize,
+    start_line: usize,
 }
 
 impl ScannerIndex {
@@ -27,8 +35,10 @@ impl ScannerIndex {
         ScannerIndex { 
             start: 0,
             current: 0,
-            byte_count: 0,
             line: 1,
+            column: 0,
+            start_column: 0,
+            start_line: 1,
         }
     }
 
@@ -40,38 +50,70 @@ impl ScannerIndex {
         self.current
     }
 
-    pub fn byte_count(&self) -> usize {
-        self.byte_count        
-    }
-
     pub fn line(&self) -> usize {
         self.line
     }
 
+    /// The line of the first character of the lexeme.
+    pub fn start_line(&self) -> usize {
+        self.start_line
+    }
+
     #[allow(dead_code)]
     pub fn print(&self) {
-        println!("ScannerIndex -- start: {}, current: {}, byte_count: {}, line: {}", 
-                  self.start, self.current, self.byte_count, self.line);
+        println!("ScannerIndex -- start: {}, current: {}, line: {}", 
+                  self.start, self.current, self.line);
     }
 
     #[allow(dead_code)]
     pub fn reset(&mut self) {
         self.start = 0;
         self.current = 0;
-        self.byte_count = 0;
         self.line = 1;
+        self.column = 0;
+        self.start_column = 0;
+        self.start_line = 1;
     }
 
     pub fn set_start(&mut self, val: usize) {
         self.start = val;
+        self.start_column = self.column;
+        self.start_line = self.line;
     }
 
+    /// Moves past a character of `val` bytes.
     pub fn inc_lexeme_indexes(&mut self, val: usize) {
-        self.current += 1;
-        self.byte_count += val;
+        self.current += val;
+        self.column += 1;
     }
 
     pub fn inc_line(&mut self) {
         self.line += 1;
+        self.column = 0;
+    }
+
+    /// An empty span at ``current``: ``here().to(&later.here())`` is the span of 
+    /// the text in between, e.g. an escape sequence inside a string.
+    pub fn here(&self) -> Span {
+        Span {
+            line: self.line,
+            column: self.column + 1,
+            end_line: self.line,
+            end_column: self.column + 1,
+            start: self.current,
+            end: self.current,
+        }
+    }
+
+    /// The span of the current lexeme, from ``start`` up to ``current``.
+    pub fn span(&self) -> Span {
+        Span {
+            line: self.start_line,
+            column: self.start_column + 1,
+            end_line: self.line,
+            end_column: self.column + 1,
+            start: self.start,
+            end: self.current,
+        }
     }
 }
@@ -0,0 +1,98 @@
+/* Date Created: 17/10/2026. */
+
+//! Not in jlox. Stack traces of runtime errors, printed the way clox prints
+//! them, see the **Printing stack traces** section in
+//! [https://craftinginterpreters.com/calls-and-functions.html](https://craftinginterpreters.com/calls-and-functions.html#printing-stack-traces):
+//!
+//! ```text
+//! [line 3] in inner()
+//! [line 7] in outer()
+//! [line 9] in script
+//! ```
+//!
+//! Native functions show as `[native] in clock()`. The tree-walker keeps a
+//! [`StackFrame`] for every call in progress, the VM uses its own call frames.
+
+use std::path::Path;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameKind {
+    // The top-level code of the script.
+    Script,
+    // The top-level code of a module, labelled with its path as imported.
+    Module(String),
+    Function(String),
+    Native(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackFrame {
+    kind: FrameKind,
+    // The line being executed: for all but the innermost frame, the line of
+    // the call to the next frame.
+    line: usize,
+    // The imported module the code is in, `None` for the script being run.
+    source: Option<Rc<Path>>,
+}
+
+impl StackFrame {
+    pub fn new(kind: FrameKind, line: usize) -> Self {
+        StackFrame { kind, line, source: None }
+    }
+
+    pub fn with_source(mut self, source: Option<Rc<Path>>) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn script() -> Self {
+        Self::new(FrameKind::Script, 0)
+    }
+
+    pub fn module(path: &str) -> Self {
+        Self::new(FrameKind::Module(path.to_string()), 0)
+    }
+
+    pub fn function(name: &str) -> Self {
+        Self::new(FrameKind::Function(name.to_string()), 0)
+    }
+
+    pub fn native(name: &str) -> Self {
+        Self::new(FrameKind::Native(name.to_string()), 0)
+    }
+
+    pub fn set_line(&mut self, line: usize) {
+        self.line = line;
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn source(&self) -> Option<&Rc<Path>> {
+        self.source.as_ref()
+    }
+
+    pub fn is_native(&self) -> bool {
+        matches!(self.kind, FrameKind::Native(_))
+    }
+}
+
+/// The stack trace of an error at `line` in the innermost frame, innermost
+/// frame first. `frames` are outermost first.
+pub fn stack_trace(frames: &[StackFrame], line: usize) -> Vec<String> {
+    frames.iter()
+        .enumerate()
+        .rev()
+        .map(|(i, frame)| {
+            let line = if i + 1 == frames.len() { line } else { frame.line };
+            match &frame.kind {
+                FrameKind::Script => format!("[line {}] in script", line),
+                FrameKind::Module(path) => format!("[line {}] in module '{}'", line, path),
+                FrameKind::Function(name) => format!("[line {}] in {}()", line, name),
+                FrameKind::Native(name) => format!("[native] in {}()", name),
+            }
+        })
+        .collect()
+}
@@ -1,19 +1,23 @@
 /// Appendix II stmt
 use std::rc::Rc;
 
-use super::token::Token;
+use super::token::{Span, Token};
 use super::expr::Expr;
 use super::lox_runtime_error::LoxRuntimeError;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Block {
     statements: Vec<Rc<Stmt>>,
+    span: Span,
 }
 
 impl Block {
-    pub fn new(statements: Vec<Rc<Stmt>>) -> Self {
+    pub fn new(statements: Vec<Rc<Stmt>>, 
+        span: Span
+    ) -> Self {
         Block {
             statements,
+            span,
         }
     }
 
@@ -21,6 +25,36 @@ impl Block {
         &self.statements
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Break {
+    keyword: Token,
+    span: Span,
+}
+
+impl Break {
+    pub fn new(keyword: Token, 
+        span: Span
+    ) -> Self {
+        Break {
+            keyword,
+            span,
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -28,17 +62,20 @@ pub struct Class {
     name: Token,
     superclass: Option<Rc<Expr>>,
     methods: Vec<Rc<Function>>,
+    span: Span,
 }
 
 impl Class {
     pub fn new(name: Token, 
         superclass: Option<Rc<Expr>>, 
-        methods: Vec<Rc<Function>>
+        methods: Vec<Rc<Function>>, 
+        span: Span
     ) -> Self {
         Class {
             name,
             superclass,
             methods,
+            span,
         }
     }
 
@@ -54,17 +91,51 @@ impl Class {
         &self.methods
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Continue {
+    keyword: Token,
+    span: Span,
+}
+
+impl Continue {
+    pub fn new(keyword: Token, 
+        span: Span
+    ) -> Self {
+        Continue {
+            keyword,
+            span,
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Expression {
     expression: Rc<Expr>,
+    span: Span,
 }
 
 impl Expression {
-    pub fn new(expression: Rc<Expr>) -> Self {
+    pub fn new(expression: Rc<Expr>, 
+        span: Span
+    ) -> Self {
         Expression {
             expression,
+            span,
         }
     }
 
@@ -72,6 +143,10 @@ impl Expression {
         &self.expression
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -79,17 +154,20 @@ pub struct Function {
     name: Token,
     params: Vec<Token>,
     body: Vec<Rc<Stmt>>,
+    span: Span,
 }
 
 impl Function {
     pub fn new(name: Token, 
         params: Vec<Token>, 
-        body: Vec<Rc<Stmt>>
+        body: Vec<Rc<Stmt>>, 
+        span: Span
     ) -> Self {
         Function {
             name,
             params,
             body,
+            span,
         }
     }
 
@@ -105,6 +183,10 @@ impl Function {
         &self.body
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -112,17 +194,20 @@ pub struct If {
     condition: Rc<Expr>,
     then_branch: Rc<Stmt>,
     else_branch: Option<Rc<Stmt>>,
+    span: Span,
 }
 
 impl If {
     pub fn new(condition: Rc<Expr>, 
         then_branch: Rc<Stmt>, 
-        else_branch: Option<Rc<Stmt>>
+        else_branch: Option<Rc<Stmt>>, 
+        span: Span
     ) -> Self {
         If {
             condition,
             then_branch,
             else_branch,
+            span,
         }
     }
 
@@ -138,17 +223,65 @@ impl If {
         &self.else_branch
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Import {
+    keyword: Token,
+    path: String,
+    name: Token,
+    span: Span,
+}
+
+impl Import {
+    pub fn new(keyword: Token, 
+        path: String, 
+        name: Token, 
+        span: Span
+    ) -> Self {
+        Import {
+            keyword,
+            path,
+            name,
+            span,
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn path(&self) -> &String {
+        &self.path
+    }
+
+    pub fn name(&self) -> &Token {
+        &self.name
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Print {
     expression: Rc<Expr>,
+    span: Span,
 }
 
 impl Print {
-    pub fn new(expression: Rc<Expr>) -> Self {
+    pub fn new(expression: Rc<Expr>, 
+        span: Span
+    ) -> Self {
         Print {
             expression,
+            span,
         }
     }
 
@@ -156,21 +289,28 @@ impl Print {
         &self.expression
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Return {
     keyword: Token,
     value: Option<Rc<Expr>>,
+    span: Span,
 }
 
 impl Return {
     pub fn new(keyword: Token, 
-        value: Option<Rc<Expr>>
+        value: Option<Rc<Expr>>, 
+        span: Span
     ) -> Self {
         Return {
             keyword,
             value,
+            span,
         }
     }
 
@@ -182,21 +322,108 @@ impl Return {
         &self.value
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Throw {
+    keyword: Token,
+    value: Rc<Expr>,
+    span: Span,
+}
+
+impl Throw {
+    pub fn new(keyword: Token, 
+        value: Rc<Expr>, 
+        span: Span
+    ) -> Self {
+        Throw {
+            keyword,
+            value,
+            span,
+        }
+    }
+
+    pub fn keyword(&self) -> &Token {
+        &self.keyword
+    }
+
+    pub fn value(&self) -> &Rc<Expr> {
+        &self.value
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+}
+
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Try {
+    body: Vec<Rc<Stmt>>,
+    catch_name: Option<Token>,
+    catch_body: Option<Vec<Rc<Stmt>>>,
+    finally_body: Option<Vec<Rc<Stmt>>>,
+    span: Span,
+}
+
+impl Try {
+    pub fn new(body: Vec<Rc<Stmt>>, 
+        catch_name: Option<Token>, 
+        catch_body: Option<Vec<Rc<Stmt>>>, 
+        finally_body: Option<Vec<Rc<Stmt>>>, 
+        span: Span
+    ) -> Self {
+        Try {
+            body,
+            catch_name,
+            catch_body,
+            finally_body,
+            span,
+        }
+    }
+
+    pub fn body(&self) -> &Vec<Rc<Stmt>> {
+        &self.body
+    }
+
+    pub fn catch_name(&self) -> &Option<Token> {
+        &self.catch_name
+    }
+
+    pub fn catch_body(&self) -> &Option<Vec<Rc<Stmt>>> {
+        &self.catch_body
+    }
+
+    pub fn finally_body(&self) -> &Option<Vec<Rc<Stmt>>> {
+        &self.finally_body
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Var {
     name: Token,
     initializer: Option<Rc<Expr>>,
+    span: Span,
 }
 
 impl Var {
     pub fn new(name: Token, 
-        initializer: Option<Rc<Expr>>
+        initializer: Option<Rc<Expr>>, 
+        span: Span
     ) -> Self {
         Var {
             name,
             initializer,
+            span,
         }
     }
 
@@ -208,21 +435,31 @@ impl Var {
         &self.initializer
     }
 
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct While {
     condition: Rc<Expr>,
     body: Rc<Stmt>,
+    increment: Option<Rc<Expr>>,
+    span: Span,
 }
 
 impl While {
     pub fn new(condition: Rc<Expr>, 
-        body: Rc<Stmt>
+        body: Rc<Stmt>, 
+        increment: Option<Rc<Expr>>, 
+        span: Span
     ) -> Self {
         While {
             condition,
             body,
+            increment,
+            span,
         }
     }
 
@@ -234,18 +471,31 @@ impl While {
         &self.body
     }
 
+    pub fn increment(&self) -> &Option<Rc<Expr>> {
+        &self.increment
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
 }
 
 // Define enum
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum Stmt {
     Block(Block),
+    Break(Break),
     Class(Class),
+    Continue(Continue),
     Expression(Expression),
     Function(Function),
     If(If),
+    Import(Import),
     Print(Print),
     Return(Return),
+    Throw(Throw),
+    Try(Try),
     Var(Var),
     While(While),
 }
@@ -253,12 +503,17 @@ pub enum Stmt {
 // Visitor Trait
 pub trait Visitor<T> {
     fn visit_block_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
+    fn visit_break_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
     fn visit_class_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
+    fn visit_continue_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
     fn visit_expression_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
     fn visit_function_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
     fn visit_if_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
+    fn visit_import_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
     fn visit_print_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
     fn visit_return_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
+    fn visit_throw_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
+    fn visit_try_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
     fn visit_var_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
     fn visit_while_stmt(&mut self, stmt: Rc<Stmt>) -> Result<T, LoxRuntimeError>;
 }
@@ -268,15 +523,40 @@ impl Stmt {
     pub fn accept<T>(stmt: Rc<Stmt>, visitor: &mut dyn Visitor<T>) -> Result<T, LoxRuntimeError> {
         match stmt.as_ref() {
             Stmt::Block(_) => visitor.visit_block_stmt(stmt),
+            Stmt::Break(_) => visitor.visit_break_stmt(stmt),
             Stmt::Class(_) => visitor.visit_class_stmt(stmt),
+            Stmt::Continue(_) => visitor.visit_continue_stmt(stmt),
             Stmt::Expression(_) => visitor.visit_expression_stmt(stmt),
             Stmt::Function(_) => visitor.visit_function_stmt(stmt),
             Stmt::If(_) => visitor.visit_if_stmt(stmt),
+            Stmt::Import(_) => visitor.visit_import_stmt(stmt),
             Stmt::Print(_) => visitor.visit_print_stmt(stmt),
             Stmt::Return(_) => visitor.visit_return_stmt(stmt),
+            Stmt::Throw(_) => visitor.visit_throw_stmt(stmt),
+            Stmt::Try(_) => visitor.visit_try_stmt(stmt),
             Stmt::Var(_) => visitor.visit_var_stmt(stmt),
             Stmt::While(_) => visitor.visit_while_stmt(stmt),
         }
     }
+
+    #[allow(dead_code)]
+    pub fn span(&self) -> &Span {
+        match self {
+            Stmt::Block(node) => node.span(),
+            Stmt::Break(node) => node.span(),
+            Stmt::Class(node) => node.span(),
+            Stmt::Continue(node) => node.span(),
+            Stmt::Expression(node) => node.span(),
+            Stmt::Function(node) => node.span(),
+            Stmt::If(node) => node.span(),
+            Stmt::Import(node) => node.span(),
+            Stmt::Print(node) => node.span(),
+            Stmt::Return(node) => node.span(),
+            Stmt::Throw(node) => node.span(),
+            Stmt::Try(node) => node.span(),
+            Stmt::Var(node) => node.span(),
+            Stmt::While(node) => node.span(),
+        }
+    }
 }
 //< Appendix II stmt
@@ -16,12 +16,42 @@ pub enum LiteralValue {
     Nil,
 }
 
+/// Where a token, or a syntax tree node, is in the source. Lines and columns
+/// are 1-based, columns count characters, not bytes. ``end_column`` and ``end``
+/// are exclusive. ``start`` and ``end`` are byte offsets, so that
+/// ``&source[span.start..span.end]`` is the source text of the span.
+#[derive(Debug, Clone, Copy, Default, Hash, Eq, PartialEq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The span from the start of ``self`` to the end of ``other``.
+    pub fn to(&self, other: &Span) -> Span {
+        Span {
+            end_line: other.end_line,
+            end_column: other.end_column,
+            end: other.end,
+            ..*self
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Token {
     type_: TokenType,
     lexeme: String,
     literal: Option<LiteralValue>,
     line: usize,
+    span: Span,
+    // Not in the book: the `///` doc comment before a `fun`, `class` or `var` 
+    // keyword or a method, see ./src/scanner.rs.
+    doc: Option<String>,
 }
 
 impl Token {
@@ -29,7 +59,17 @@ impl Token {
         lexeme: String, 
         literal: Option<LiteralValue>, 
         line: usize) -> Self {
-            Token { type_, lexeme, literal, line }
+            Token { type_, lexeme, literal, line, span: Span::default(), doc: None }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+
+    pub fn with_doc(mut self, doc: String) -> Self {
+        self.doc = Some(doc);
+        self
     }
 
     pub fn token_type(&self) -> TokenType {
@@ -47,6 +87,17 @@ impl Token {
     pub fn line(&self) -> usize {
         self.line
     }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// The doc comment of the declaration this keyword starts, or this name 
+    /// declares: its `///` lines, without the slashes, joined with newlines. 
+    /// The names in `Stmt::Function`, `Stmt::Class` and `Stmt::Var` have it.
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
 }
 
 impl fmt::Display for Token {
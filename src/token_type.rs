@@ -12,6 +12,9 @@ pub enum TokenType {
     RightParen, 
     LeftBrace, 
     RightBrace,
+    LeftBracket, 
+    RightBracket,
+    Colon, 
     Comma, 
     Dot, 
     Minus, 
@@ -37,19 +40,26 @@ pub enum TokenType {
 
     // Keywords.
     And, 
+    Break, 
+    Catch, 
     Class, 
+    Continue, 
     Else, 
     False, 
+    Finally, 
     Fun, 
     For, 
     If, 
+    Import, 
     Nil, 
     Or,
     Print, 
     Return, 
     Super, 
     This, 
+    Throw, 
     True, 
+    Try, 
     Var, 
     While,
     Eof
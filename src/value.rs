@@ -2,14 +2,33 @@
 
 //! Lox supported data types. This is Rust-specific, this module is not in the 
 //! original Java version.
+//!
+//! Rust values convert into `Value` with `From`, and back with `TryFrom`:
+//!
+//! ```
+//! use rlox::value::Value;
+//!
+//! let value = Value::from("abc");
+//! assert_eq!(String::try_from(value).unwrap(), "abc");
+//! assert_eq!(Value::from(None::<f64>), Value::Nil);
+//! assert_eq!(f64::try_from(Value::Nil).unwrap_err().to_string(), 
+//!     "Expected a number but got nil.");
+//! ```
 
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::fmt;
 
 use super::lox_callable::LoxCallable;
 use super::lox_instance::LoxInstance;
+use super::lox_map::LoxMap;
+use super::lox_module::LoxModule;
+use super::lox_host::HostObject;
+use super::lox_error::LoxError;
+use super::lox_error_helper::sys_error;
+use super::lox_runtime_error::LoxRuntimeError;
 
 // Rust-specific.
 
@@ -18,6 +37,9 @@ use super::lox_instance::LoxInstance;
 //     * cargo test value::tests
 //
 //     * cargo test value::tests::value_comparison_and_clone -- --exact [--nocapture]
+//     * cargo test value::tests::list_equality_is_identity -- --exact [--nocapture]
+//     * cargo test value::tests::from_rust_values -- --exact [--nocapture]
+//     * cargo test value::tests::try_from_values -- --exact [--nocapture]
 //
 
 
@@ -31,10 +53,36 @@ pub enum Value {
     Nil,
     LoxCallable(Rc<dyn LoxCallable>),
     LoxInstance(Rc<RefCell<LoxInstance>>),
+    // Not in the book: a mutable list, shared by reference like instances.
+    List(Rc<RefCell<Vec<Value>>>),
+    // Not in the book: a mutable map keyed by strings, numbers and booleans.
+    Map(Rc<RefCell<LoxMap>>),
+    // Not in the book: an imported module, see ./src/lox_module.rs.
+    Module(Rc<LoxModule>),
+    // Not in the book: a Rust object, see ./src/lox_host.rs.
+    Host(Rc<dyn HostObject>),
 }
 
 pub type ValueMap = HashMap<String, Value>;
 
+impl Value {
+    /// The name of the Lox type, used in error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Boolean(_) => "boolean",
+            Value::Nil => "nil",
+            Value::LoxCallable(_) => "function",
+            Value::LoxInstance(_) => "instance",
+            Value::List(_) => "list",
+            Value::Map(_) => "map",
+            Value::Module(_) => "module",
+            Value::Host(_) => "instance",
+        }
+    }
+}
+
 impl std::fmt::Debug for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -44,6 +92,12 @@ impl std::fmt::Debug for Value {
             Value::Nil => write!(f, "Nil"),
             Value::LoxInstance(_) => write!(f, "LoxInstance"),
             Value::LoxCallable(_) => write!(f, "LoxCallable"),
+            // Through the same guard as `Display`, for lists and maps which 
+            // contain themselves.
+            Value::List(_) => write!(f, "List({})", self.nested_to_string(&mut Vec::new())),
+            Value::Map(_) => write!(f, "Map({})", self.nested_to_string(&mut Vec::new())),
+            Value::Module(module) => write!(f, "Module({:?})", module),
+            Value::Host(host) => write!(f, "Host({})", host.name()),
         }
     }
 }
@@ -61,6 +115,11 @@ impl PartialEq for Value {
             (Value::LoxCallable(a), Value::LoxCallable(b)) =>
                 std::ptr::eq(a.as_ref(), b.as_ref()),
             (Value::LoxInstance(i1), Value::LoxInstance(i2)) => i1 == i2,
+            // Lists are equal only when they are the same list.
+            (Value::List(l1), Value::List(l2)) => Rc::ptr_eq(l1, l2),
+            (Value::Map(m1), Value::Map(m2)) => Rc::ptr_eq(m1, m2),
+            (Value::Module(m1), Value::Module(m2)) => Rc::ptr_eq(m1, m2),
+            (Value::Host(h1), Value::Host(h2)) => Rc::ptr_eq(h1, h2),
             _ => false,
         }
     }
@@ -75,6 +134,189 @@ impl fmt::Display for Value {
             Value::Nil => write!(f, "nil"),
             Value::LoxCallable(callable) => write!(f, "{}", callable),
             Value::LoxInstance(instance) => write!(f, "{}", instance.borrow().to_string()),
+            Value::List(_) | Value::Map(_) => write!(f, "{}", self.nested_to_string(&mut Vec::new())),
+            Value::Module(module) => write!(f, "{}", module),
+            Value::Host(host) => write!(f, "{} instance", host.name()),
+        }
+    }
+}
+
+impl Value {
+    // `Display` for lists and maps. `printing` has the lists and maps being 
+    // printed, by address: one which contains itself prints as `[...]` or 
+    // `{...}` there, rather than overflowing the stack.
+    fn nested_to_string(&self, printing: &mut Vec<*const ()>) -> String {
+        match self {
+            Value::List(list) => {
+                let address = Rc::as_ptr(list) as *const ();
+                if printing.contains(&address) {
+                    return "[...]".to_string();
+                }
+                printing.push(address);
+                let elements: Vec<String> = list.borrow()
+                    .iter()
+                    .map(|element| element.nested_to_string(printing))
+                    .collect();
+                printing.pop();
+                format!("[{}]", elements.join(", "))
+            }
+            Value::Map(map) => {
+                let address = Rc::as_ptr(map) as *const ();
+                if printing.contains(&address) {
+                    return "{...}".to_string();
+                }
+                printing.push(address);
+                let entries: Vec<String> = map.borrow()
+                    .entries()
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value.nested_to_string(printing)))
+                    .collect();
+                printing.pop();
+                format!("{{{}}}", entries.join(", "))
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+// Map keys. Follows `impl Hash for LiteralValue` in ./src/token.rs.
+//
+// Only numbers, strings and booleans are valid map keys, see 
+// `LoxMap::check_key()`. The remaining variants hash their tag only: this is 
+// still consistent with `PartialEq`, it just never happens in practice.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Value::Number(n) => {
+                state.write_u8(0); // tag for Number
+                // 0.0 == -0.0, so they must hash the same.
+                let n = if *n == 0.0 { 0.0 } else { *n };
+                state.write_u64(n.to_bits()); // safe hashable representation
+            }
+            Value::String(s) => {
+                state.write_u8(1);
+                s.hash(state);
+            }
+            Value::Boolean(b) => {
+                state.write_u8(2);
+                b.hash(state);
+            }
+            Value::Nil => state.write_u8(3),
+            Value::LoxCallable(_) => state.write_u8(4),
+            Value::LoxInstance(_) => state.write_u8(5),
+            Value::List(_) => state.write_u8(6),
+            Value::Map(_) => state.write_u8(7),
+            Value::Module(_) => state.write_u8(8),
+            Value::Host(_) => state.write_u8(9),
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Boolean(value)
+    }
+}
+
+// None is nil.
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Nil, Into::into)
+    }
+}
+
+/// A `Value` of the wrong Lox type for `TryFrom`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionError {
+    pub expected: &'static str,
+    pub actual: &'static str,
+}
+
+impl ConversionError {
+    fn new(expected: &'static str, value: &Value) -> Self {
+        ConversionError { expected, actual: value.type_name() }
+    }
+}
+
+// "a number", "an instance", but just "nil".
+fn with_article(type_name: &str) -> String {
+    match type_name {
+        "nil" => type_name.to_string(),
+        "instance" => format!("an {}", type_name),
+        _ => format!("a {}", type_name),
+    }
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Expected {} but got {}.", with_article(self.expected), with_article(self.actual))
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+// So that native functions can convert their arguments with `?`.
+impl From<ConversionError> for LoxError {
+    fn from(err: ConversionError) -> Self {
+        sys_error("", &err.to_string())
+    }
+}
+
+impl From<ConversionError> for LoxRuntimeError {
+    fn from(err: ConversionError) -> Self {
+        LoxRuntimeError::Error(err.into())
+    }
+}
+
+impl TryFrom<Value> for f64 {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Number(n) => Ok(n),
+            _ => Err(ConversionError::new("number", &value)),
+        }
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(s) => Ok(s),
+            _ => Err(ConversionError::new("string", &value)),
+        }
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Boolean(b) => Ok(b),
+            _ => Err(ConversionError::new("boolean", &value)),
         }
     }
 }
@@ -89,4 +331,40 @@ mod tests {
         let b = a.clone();
         assert_eq!(a, b);
     }
+
+    #[test]
+    fn list_equality_is_identity() {
+        let a = Value::List(Rc::new(RefCell::new(vec![Value::Number(1.0)])));
+        let b = a.clone();
+        let c = Value::List(Rc::new(RefCell::new(vec![Value::Number(1.0)])));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn from_rust_values() {
+        assert_eq!(Value::from(1.5), Value::Number(1.5));
+        assert_eq!(Value::from("abc"), Value::String("abc".to_string()));
+        assert_eq!(Value::from("abc".to_string()), Value::String("abc".to_string()));
+        assert_eq!(Value::from(true), Value::Boolean(true));
+        assert_eq!(Value::from(Some(2.0)), Value::Number(2.0));
+        assert_eq!(Value::from(None::<&str>), Value::Nil);
+    }
+
+    #[test]
+    fn try_from_values() {
+        assert_eq!(f64::try_from(Value::Number(1.5)), Ok(1.5));
+        assert_eq!(String::try_from(Value::from("abc")), Ok("abc".to_string()));
+        assert_eq!(bool::try_from(Value::Boolean(false)), Ok(false));
+
+        let err = f64::try_from(Value::from("abc")).unwrap_err();
+        assert_eq!(err, ConversionError { expected: "number", actual: "string" });
+        assert_eq!(err.to_string(), "Expected a number but got a string.");
+
+        let err = bool::try_from(Value::Nil).unwrap_err();
+        assert_eq!(err.to_string(), "Expected a boolean but got nil.");
+
+        let list = Value::List(Rc::new(RefCell::new(vec![])));
+        assert_eq!(String::try_from(list).unwrap_err().actual, "list");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,157 @@
+/* Date Created: 17/10/2026. */
+
+//! Chapter 14: [Chunks of Bytecode](https://craftinginterpreters.com/chunks-of-bytecode.html).
+//!
+//! Operands are one byte for argument counts, and two bytes, big-endian, for 
+//! everything else: constant pool indexes, stack slots, token indexes and 
+//! jump offsets.
+
+use super::value::Value;
+use crate::token::Token;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum OpCode {
+    Constant,
+    Nil,
+    True,
+    False,
+    Pop,
+    // Pops the number of values given by its operand, closing any upvalues 
+    // which capture them: used by `break`, `continue` and `return`.
+    PopN,
+    GetLocal,
+    SetLocal,
+    GetGlobal,
+    DefineGlobal,
+    SetGlobal,
+    GetUpvalue,
+    SetUpvalue,
+    GetProperty,
+    // Fails when the object is not an instance, before the value is evaluated.
+    CheckInstance,
+    SetProperty,
+    GetSuper,
+    GetIndex,
+    // Fails when the object can't be indexed by the key, before the value is 
+    // evaluated.
+    CheckIndex,
+    SetIndex,
+    CheckKey,
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Not,
+    Negate,
+    Print,
+    Jump,
+    JumpIfFalse,
+    Loop,
+    Call,
+    Invoke,
+    Closure,
+    CloseUpvalue,
+    // Moves the value being returned aside while `finally` blocks run.
+    Stash,
+    Unstash,
+    Return,
+    Class,
+    CheckSuperclass,
+    Inherit,
+    Method,
+    List,
+    Map,
+    Throw,
+    // Rethrows at the end of a `finally` block: the error is still reported 
+    // where the value was first thrown.
+    Rethrow,
+    Try,
+    EndTry,
+    Import,
+}
+
+// Decodes bytes into opcodes. A `static` rather than a `const`, which would be
+// copied on every use.
+static OPCODES: [OpCode; 55] = [
+    OpCode::Constant, OpCode::Nil, OpCode::True, OpCode::False, OpCode::Pop,
+    OpCode::PopN, OpCode::GetLocal, OpCode::SetLocal, OpCode::GetGlobal,
+    OpCode::DefineGlobal, OpCode::SetGlobal, OpCode::GetUpvalue, OpCode::SetUpvalue,
+    OpCode::GetProperty, OpCode::CheckInstance, OpCode::SetProperty,
+    OpCode::GetSuper, OpCode::GetIndex, OpCode::CheckIndex, OpCode::SetIndex,
+    OpCode::CheckKey, OpCode::Equal, OpCode::NotEqual, OpCode::Greater,
+    OpCode::GreaterEqual, OpCode::Less, OpCode::LessEqual, OpCode::Add,
+    OpCode::Subtract, OpCode::Multiply, OpCode::Divide, OpCode::Not, OpCode::Negate,
+    OpCode::Print, OpCode::Jump, OpCode::JumpIfFalse, OpCode::Loop, OpCode::Call,
+    OpCode::Invoke, OpCode::Closure, OpCode::CloseUpvalue, OpCode::Stash,
+    OpCode::Unstash, OpCode::Return, OpCode::Class, OpCode::CheckSuperclass,
+    OpCode::Inherit, OpCode::Method, OpCode::List, OpCode::Map, OpCode::Throw,
+    OpCode::Rethrow, OpCode::Try, OpCode::EndTry, OpCode::Import,
+];
+
+impl OpCode {
+    pub fn from_byte(byte: u8) -> OpCode {
+        OPCODES[byte as usize]
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Chunk {
+    code: Vec<u8>,
+    constants: Vec<Value>,
+    // The tokens instructions report runtime errors at.
+    tokens: Vec<Token>,
+    // Per byte of `code`: index into `tokens`.
+    positions: Vec<u32>,
+}
+
+impl Chunk {
+    pub fn code(&self) -> &Vec<u8> {
+        &self.code
+    }
+
+    pub fn write(&mut self, byte: u8, token: &Token) {
+        if self.tokens.last() != Some(token) {
+            self.tokens.push(token.clone());
+        }
+        self.code.push(byte);
+        self.positions.push((self.tokens.len() - 1) as u32);
+    }
+
+    pub fn patch(&mut self, offset: usize, byte: u8) {
+        self.code[offset] = byte;
+    }
+
+    pub fn add_constant(&mut self, value: Value) -> usize {
+        self.constants.push(value);
+        self.constants.len() - 1
+    }
+
+    pub fn constant(&self, index: usize) -> &Value {
+        &self.constants[index]
+    }
+
+    pub fn constants_len(&self) -> usize {
+        self.constants.len()
+    }
+
+    pub fn add_token(&mut self, token: &Token) -> usize {
+        self.tokens.push(token.clone());
+        self.tokens.len() - 1
+    }
+
+    pub fn token(&self, index: usize) -> &Token {
+        &self.tokens[index]
+    }
+
+    /// The token of the instruction which `offset` belongs to.
+    pub fn token_at(&self, offset: usize) -> &Token {
+        &self.tokens[self.positions[offset] as usize]
+    }
+}
@@ -0,0 +1,920 @@
+/* Date Created: 17/10/2026. */
+
+//! Compiles the AST into bytecode. Chapter 17: [Compiling Expressions](https://craftinginterpreters.com/compiling-expressions.html)
+//! onwards.
+//!
+//! Unlike clox, the compiler does not parse: it walks the statements from
+//! ./src/parser.rs, which have already been checked by ./src/resolver.rs.
+//! It still resolves local variables and upvalues itself, the same way as
+//! clox, since the VM addresses them by stack slot.
+
+use std::rc::Rc;
+use std::collections::HashMap;
+
+use super::chunk::OpCode;
+use super::value::{Value, ObjFunction};
+use crate::lox_error::LoxError;
+use crate::lox_error_helper::runtime_error;
+use crate::lox_runtime_error::LoxRuntimeError;
+use crate::{expr, expr::Expr};
+use crate::{stmt, stmt::Stmt};
+use crate::{unwrap_expr, unwrap_stmt};
+use crate::token::{Token, LiteralValue};
+use crate::token_type::TokenType;
+
+#[derive(Copy, Clone, PartialEq)]
+enum FunctionType {
+    Script,
+    Function,
+    Method,
+    Initializer,
+}
+
+struct Local {
+    name: String,
+    depth: usize,
+    is_captured: bool,
+}
+
+#[derive(PartialEq)]
+struct Upvalue {
+    index: u16,
+    is_local: bool,
+}
+
+struct Loop {
+    // Number of locals and of enclosing `try` statements when the loop starts.
+    locals: usize,
+    tries: usize,
+    breaks: Vec<usize>,
+    continues: Vec<usize>,
+}
+
+// An enclosing `try` statement, or `catch` clause. `break`, `continue` and
+// `return` jumping out of it must remove its handler, and run its `finally`
+// block on the way out.
+#[derive(Clone)]
+struct Try {
+    locals: usize,
+    finally_body: Option<Vec<Rc<Stmt>>>,
+}
+
+struct FunctionState {
+    function: ObjFunction,
+    function_type: FunctionType,
+    locals: Vec<Local>,
+    upvalues: Vec<Upvalue>,
+    scope_depth: usize,
+    loops: Vec<Loop>,
+    tries: Vec<Try>,
+    names: HashMap<String, u16>,
+}
+
+impl FunctionState {
+    fn new(name: &str, function_type: FunctionType) -> Self {
+        // Slot zero holds the function being called, or `this` in methods.
+        let slot_zero = match function_type {
+            FunctionType::Method | FunctionType::Initializer => "this",
+            _ => "",
+        };
+
+        FunctionState {
+            function: ObjFunction { name: name.to_string(), ..Default::default() },
+            function_type,
+            locals: vec![Local { name: slot_zero.to_string(), depth: 0, is_captured: false }],
+            upvalues: vec![],
+            scope_depth: 0,
+            loops: vec![],
+            tries: vec![],
+            names: HashMap::new(),
+        }
+    }
+}
+
+pub struct Compiler {
+    states: Vec<FunctionState>,
+    // The token instructions are written with, reported by runtime errors.
+    token: Token,
+}
+
+impl Compiler {
+    /// Compiles a script, or a module, into its top-level function.
+    pub fn compile(statements: &[Rc<Stmt>]) -> Result<Rc<ObjFunction>, LoxError> {
+        let mut compiler = Compiler {
+            states: vec![FunctionState::new("", FunctionType::Script)],
+            token: Token::new(TokenType::Eof, String::new(), None, 0),
+        };
+
+        let result: Result<(), LoxRuntimeError> = statements.iter().try_for_each(|stmt| {
+            let offset = compiler.current().function.chunk.code().len();
+            compiler.current_mut().function.statements.push(offset);
+            compiler.statement(stmt)
+        });
+
+        match result {
+            Ok(()) => {
+                let offset = compiler.current().function.chunk.code().len();
+                compiler.current_mut().function.statements.push(offset);
+                compiler.emit_return();
+                let state = compiler.states.pop().expect("Script state");
+                Ok(Rc::new(state.function))
+            }
+            Err(LoxRuntimeError::Error(err)) => Err(err),
+            Err(err) => Err(LoxError::from(err)),
+        }
+    }
+
+    fn current(&self) -> &FunctionState {
+        self.states.last().expect("Function state")
+    }
+
+    fn current_mut(&mut self) -> &mut FunctionState {
+        self.states.last_mut().expect("Function state")
+    }
+
+    fn statement(&mut self, stmt: &Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        Stmt::accept(Rc::clone(stmt), self)
+    }
+
+    fn expression(&mut self, expr: &Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        Expr::accept(Rc::clone(expr), self)
+    }
+
+    fn block(&mut self, statements: &[Rc<Stmt>]) -> Result<(), LoxRuntimeError> {
+        self.begin_scope();
+        for stmt in statements {
+            self.statement(stmt)?;
+        }
+        self.end_scope();
+        Ok(())
+    }
+
+    // ---- Emitting bytecode.
+
+    fn emit_byte(&mut self, byte: u8) {
+        let token = self.token.clone();
+        self.current_mut().function.chunk.write(byte, &token);
+    }
+
+    fn emit_op(&mut self, op: OpCode) {
+        self.emit_byte(op as u8);
+    }
+
+    fn emit_u16(&mut self, value: u16) {
+        self.emit_byte((value >> 8) as u8);
+        self.emit_byte((value & 0xff) as u8);
+    }
+
+    fn emit_op_u16(&mut self, op: OpCode, value: u16) {
+        self.emit_op(op);
+        self.emit_u16(value);
+    }
+
+    fn emit_return(&mut self) {
+        if self.current().function_type == FunctionType::Initializer {
+            self.emit_op_u16(OpCode::GetLocal, 0);
+        } else {
+            self.emit_op(OpCode::Nil);
+        }
+        self.emit_op(OpCode::Return);
+    }
+
+    fn make_constant(&mut self, value: Value) -> Result<u16, LoxRuntimeError> {
+        let chunk = &mut self.current_mut().function.chunk;
+        if chunk.constants_len() > u16::MAX as usize {
+            return Err(runtime_error(&self.token, "Too many constants in one chunk."));
+        }
+        Ok(chunk.add_constant(value) as u16)
+    }
+
+    fn emit_constant(&mut self, value: Value) -> Result<(), LoxRuntimeError> {
+        let constant = self.make_constant(value)?;
+        self.emit_op_u16(OpCode::Constant, constant);
+        Ok(())
+    }
+
+    // Names of globals and properties. Each is added to the constants once.
+    fn identifier_constant(&mut self, name: &str) -> Result<u16, LoxRuntimeError> {
+        if let Some(&constant) = self.current().names.get(name) {
+            return Ok(constant);
+        }
+        let constant = self.make_constant(Value::String(Rc::from(name)))?;
+        self.current_mut().names.insert(name.to_string(), constant);
+        Ok(constant)
+    }
+
+    fn emit_jump(&mut self, op: OpCode) -> usize {
+        self.emit_op_u16(op, 0xffff);
+        self.current().function.chunk.code().len() - 2
+    }
+
+    fn patch_jump(&mut self, offset: usize) -> Result<(), LoxRuntimeError> {
+        // -2 to adjust for the bytecode for the jump offset itself.
+        let jump = self.current().function.chunk.code().len() - offset - 2;
+        if jump > u16::MAX as usize {
+            return Err(runtime_error(&self.token, "Too much code to jump over."));
+        }
+        let chunk = &mut self.current_mut().function.chunk;
+        chunk.patch(offset, (jump >> 8) as u8);
+        chunk.patch(offset + 1, (jump & 0xff) as u8);
+        Ok(())
+    }
+
+    fn emit_loop(&mut self, loop_start: usize) -> Result<(), LoxRuntimeError> {
+        self.emit_op(OpCode::Loop);
+        let offset = self.current().function.chunk.code().len() - loop_start + 2;
+        if offset > u16::MAX as usize {
+            return Err(runtime_error(&self.token, "Loop body too large."));
+        }
+        self.emit_u16(offset as u16);
+        Ok(())
+    }
+
+    fn emit_pops(&mut self, count: usize) {
+        if count > 0 {
+            self.emit_op_u16(OpCode::PopN, count as u16);
+        }
+    }
+
+    // ---- Variables.
+
+    fn begin_scope(&mut self) {
+        self.current_mut().scope_depth += 1;
+    }
+
+    fn end_scope(&mut self) {
+        self.current_mut().scope_depth -= 1;
+
+        loop {
+            let state = self.current();
+            match state.locals.last() {
+                Some(local) if local.depth > state.scope_depth => {
+                    let op = if local.is_captured { OpCode::CloseUpvalue } else { OpCode::Pop };
+                    self.emit_op(op);
+                    self.current_mut().locals.pop();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn add_local(&mut self, name: &str) -> Result<(), LoxRuntimeError> {
+        if self.current().locals.len() > u16::MAX as usize {
+            return Err(runtime_error(&self.token, "Too many local variables in function."));
+        }
+        let depth = self.current().scope_depth;
+        self.current_mut().locals.push(Local { name: name.to_string(), depth, is_captured: false });
+        Ok(())
+    }
+
+    // Defines a variable whose value is on top of the stack.
+    fn define_variable(&mut self, name: &Token) -> Result<(), LoxRuntimeError> {
+        if self.current().scope_depth > 0 {
+            return self.add_local(name.lexeme());
+        }
+        let constant = self.identifier_constant(name.lexeme())?;
+        self.emit_op_u16(OpCode::DefineGlobal, constant);
+        Ok(())
+    }
+
+    fn resolve_local(&self, state: usize, name: &str) -> Option<u16> {
+        self.states[state].locals
+            .iter()
+            .rposition(|local| local.name == name)
+            .map(|slot| slot as u16)
+    }
+
+    fn resolve_upvalue(&mut self, state: usize, name: &str) -> Result<Option<u16>, LoxRuntimeError> {
+        if state == 0 {
+            return Ok(None);
+        }
+
+        if let Some(local) = self.resolve_local(state - 1, name) {
+            self.states[state - 1].locals[local as usize].is_captured = true;
+            return self.add_upvalue(state, local, true).map(Some);
+        }
+
+        match self.resolve_upvalue(state - 1, name)? {
+            Some(upvalue) => self.add_upvalue(state, upvalue, false).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn add_upvalue(&mut self, state: usize, index: u16, is_local: bool) -> Result<u16, LoxRuntimeError> {
+        let upvalue = Upvalue { index, is_local };
+        let upvalues = &mut self.states[state].upvalues;
+
+        if let Some(existing) = upvalues.iter().position(|u| *u == upvalue) {
+            return Ok(existing as u16);
+        }
+        if upvalues.len() > u16::MAX as usize {
+            return Err(runtime_error(&self.token, "Too many closure variables in function."));
+        }
+        upvalues.push(upvalue);
+        Ok((upvalues.len() - 1) as u16)
+    }
+
+    fn named_variable(&mut self, name: &str, assign: bool) -> Result<(), LoxRuntimeError> {
+        let state = self.states.len() - 1;
+
+        let (op, operand) = if let Some(slot) = self.resolve_local(state, name) {
+            (if assign { OpCode::SetLocal } else { OpCode::GetLocal }, slot)
+        } else if let Some(upvalue) = self.resolve_upvalue(state, name)? {
+            (if assign { OpCode::SetUpvalue } else { OpCode::GetUpvalue }, upvalue)
+        } else {
+            let constant = self.identifier_constant(name)?;
+            (if assign { OpCode::SetGlobal } else { OpCode::GetGlobal }, constant)
+        };
+
+        self.emit_op_u16(op, operand);
+        Ok(())
+    }
+
+    // ---- Functions and classes.
+
+    fn function(&mut self, declaration: &stmt::Function,
+        function_type: FunctionType) -> Result<(), LoxRuntimeError> {
+
+        self.states.push(FunctionState::new(declaration.name().lexeme(), function_type));
+        self.begin_scope();
+
+        for param in declaration.params() {
+            self.add_local(param.lexeme())?;
+        }
+        self.current_mut().function.arity = declaration.params().len();
+
+        for stmt in declaration.body() {
+            self.statement(stmt)?;
+        }
+        self.emit_return();
+
+        let mut state = self.states.pop().expect("Function state");
+        state.function.upvalue_count = state.upvalues.len();
+
+        self.token = declaration.name().clone();
+        let constant = self.make_constant(Value::Function(Rc::new(state.function)))?;
+        self.emit_op_u16(OpCode::Closure, constant);
+        for upvalue in state.upvalues {
+            self.emit_byte(upvalue.is_local as u8);
+            self.emit_u16(upvalue.index);
+        }
+        Ok(())
+    }
+
+    // ---- Jumping out of `try` statements.
+
+    // Leaves the enclosing `try` statements, innermost first, down to `until`:
+    // pops their locals, removes their handlers and runs their `finally`
+    // blocks. Returns the number of locals left on the stack.
+    fn unwind_tries(&mut self, until: usize) -> Result<usize, LoxRuntimeError> {
+        let mut locals = self.current().locals.len();
+
+        for i in (until..self.current().tries.len()).rev() {
+            let try_ = self.current().tries[i].clone();
+
+            self.emit_pops(locals - try_.locals);
+            locals = try_.locals;
+            self.emit_op(OpCode::EndTry);
+
+            if let Some(finally_body) = try_.finally_body {
+                // The `finally` block is compiled where the `try` statement
+                // is: the locals, loops and handlers inside it are not in scope.
+                let state = self.current_mut();
+                let locals_inside = state.locals.split_off(try_.locals);
+                let tries_inside = state.tries.split_off(i);
+                let loop_count = state.loops.iter().take_while(|l| l.tries <= i).count();
+                let loops_inside = state.loops.split_off(loop_count);
+
+                self.block(&finally_body)?;
+
+                let state = self.current_mut();
+                state.locals.extend(locals_inside);
+                state.tries.extend(tries_inside);
+                state.loops.extend(loops_inside);
+            }
+        }
+
+        Ok(locals)
+    }
+
+    // `break` and `continue`: leaves the loop body, and returns the offset
+    // of the jump to patch.
+    fn exit_loop_body(&mut self) -> Result<usize, LoxRuntimeError> {
+        let (loop_locals, loop_tries) = {
+            let current_loop = self.current().loops.last().expect("Resolver checks loops");
+            (current_loop.locals, current_loop.tries)
+        };
+
+        let locals = self.unwind_tries(loop_tries)?;
+        self.emit_pops(locals - loop_locals);
+        Ok(self.emit_jump(OpCode::Jump))
+    }
+}
+
+impl expr::Visitor<()> for Compiler {
+    fn visit_assign_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let assign = unwrap_expr!(expr, Assign);
+
+        self.expression(assign.value())?;
+        self.token = assign.name().clone();
+        self.named_variable(assign.name().lexeme(), true)
+    }
+
+    fn visit_binary_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let binary = unwrap_expr!(expr, Binary);
+
+        self.expression(binary.left())?;
+        self.expression(binary.right())?;
+
+        self.token = binary.operator().clone();
+        let op = match binary.operator().token_type() {
+            TokenType::BangEqual => OpCode::NotEqual,
+            TokenType::EqualEqual => OpCode::Equal,
+            TokenType::Greater => OpCode::Greater,
+            TokenType::GreaterEqual => OpCode::GreaterEqual,
+            TokenType::Less => OpCode::Less,
+            TokenType::LessEqual => OpCode::LessEqual,
+            TokenType::Plus => OpCode::Add,
+            TokenType::Minus => OpCode::Subtract,
+            TokenType::Star => OpCode::Multiply,
+            TokenType::Slash => OpCode::Divide,
+            _ => unreachable!("Not a binary operator"),
+        };
+        self.emit_op(op);
+        Ok(())
+    }
+
+    fn visit_call_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let call = unwrap_expr!(expr, Call);
+
+        if call.arguments().len() > u8::MAX as usize {
+            return Err(runtime_error(call.paren(), "Can't have more than 255 arguments."));
+        }
+
+        // `object.method(...)` is invoked without creating a bound method.
+        if let Expr::Get(get) = call.callee().as_ref() {
+            self.expression(get.object())?;
+            for argument in call.arguments() {
+                self.expression(argument)?;
+            }
+
+            self.token = call.paren().clone();
+            let name = self.identifier_constant(get.name().lexeme())?;
+            let name_token = self.current_mut().function.chunk.add_token(get.name());
+            if name_token > u16::MAX as usize {
+                return Err(runtime_error(call.paren(), "Too many tokens in one chunk."));
+            }
+            self.emit_op_u16(OpCode::Invoke, name);
+            self.emit_byte(call.arguments().len() as u8);
+            self.emit_u16(name_token as u16);
+            return Ok(());
+        }
+
+        self.expression(call.callee())?;
+        for argument in call.arguments() {
+            self.expression(argument)?;
+        }
+
+        self.token = call.paren().clone();
+        self.emit_op(OpCode::Call);
+        self.emit_byte(call.arguments().len() as u8);
+        Ok(())
+    }
+
+    fn visit_get_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let get = unwrap_expr!(expr, Get);
+
+        self.expression(get.object())?;
+        self.token = get.name().clone();
+        let name = self.identifier_constant(get.name().lexeme())?;
+        self.emit_op_u16(OpCode::GetProperty, name);
+        Ok(())
+    }
+
+    fn visit_grouping_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let grouping = unwrap_expr!(expr, Grouping);
+        self.expression(grouping.expression())
+    }
+
+    fn visit_index_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let index = unwrap_expr!(expr, Index);
+
+        self.expression(index.object())?;
+        self.expression(index.index())?;
+        self.token = index.bracket().clone();
+        self.emit_op(OpCode::GetIndex);
+        Ok(())
+    }
+
+    fn visit_indexset_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let index_set = unwrap_expr!(expr, IndexSet);
+
+        self.expression(index_set.object())?;
+        self.expression(index_set.index())?;
+        // The object and the key are checked before the value is evaluated.
+        self.token = index_set.bracket().clone();
+        self.emit_op(OpCode::CheckIndex);
+        self.expression(index_set.value())?;
+        self.token = index_set.bracket().clone();
+        self.emit_op(OpCode::SetIndex);
+        Ok(())
+    }
+
+    fn visit_list_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let list = unwrap_expr!(expr, List);
+
+        for element in list.elements() {
+            self.expression(element)?;
+        }
+        self.emit_op_u16(OpCode::List, list.elements().len() as u16);
+        Ok(())
+    }
+
+    fn visit_literal_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let literal = unwrap_expr!(expr, Literal);
+
+        match literal.value() {
+            LiteralValue::Number(n) => self.emit_constant(Value::Number(*n))?,
+            LiteralValue::String(s) => self.emit_constant(Value::String(Rc::from(s.as_str())))?,
+            LiteralValue::Boolean(true) => self.emit_op(OpCode::True),
+            LiteralValue::Boolean(false) => self.emit_op(OpCode::False),
+            LiteralValue::Nil => self.emit_op(OpCode::Nil),
+        }
+        Ok(())
+    }
+
+    fn visit_logical_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let logical = unwrap_expr!(expr, Logical);
+
+        self.expression(logical.left())?;
+
+        if logical.operator().token_type() == TokenType::And {
+            let end_jump = self.emit_jump(OpCode::JumpIfFalse);
+            self.emit_op(OpCode::Pop);
+            self.expression(logical.right())?;
+            self.patch_jump(end_jump)
+        } else {
+            let else_jump = self.emit_jump(OpCode::JumpIfFalse);
+            let end_jump = self.emit_jump(OpCode::Jump);
+            self.patch_jump(else_jump)?;
+            self.emit_op(OpCode::Pop);
+            self.expression(logical.right())?;
+            self.patch_jump(end_jump)
+        }
+    }
+
+    fn visit_map_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let map = unwrap_expr!(expr, Map);
+
+        for (key, value) in map.keys().iter().zip(map.values()) {
+            self.expression(key)?;
+            self.token = map.brace().clone();
+            self.emit_op(OpCode::CheckKey);
+            self.expression(value)?;
+        }
+        self.emit_op_u16(OpCode::Map, map.keys().len() as u16);
+        Ok(())
+    }
+
+    fn visit_set_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let set = unwrap_expr!(expr, Set);
+
+        self.expression(set.object())?;
+        // The object is checked before the value is evaluated.
+        self.token = set.name().clone();
+        self.emit_op(OpCode::CheckInstance);
+        self.expression(set.value())?;
+        self.token = set.name().clone();
+        let name = self.identifier_constant(set.name().lexeme())?;
+        self.emit_op_u16(OpCode::SetProperty, name);
+        Ok(())
+    }
+
+    fn visit_super_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_expr!(expr, Super);
+
+        self.token = inner.method().clone();
+        self.named_variable("this", false)?;
+        self.named_variable("super", false)?;
+        let name = self.identifier_constant(inner.method().lexeme())?;
+        self.emit_op_u16(OpCode::GetSuper, name);
+        Ok(())
+    }
+
+    fn visit_this_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let this = unwrap_expr!(expr, This);
+
+        self.token = this.keyword().clone();
+        self.named_variable("this", false)
+    }
+
+    fn visit_unary_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let unary = unwrap_expr!(expr, Unary);
+
+        self.expression(unary.right())?;
+        self.token = unary.operator().clone();
+        match unary.operator().token_type() {
+            TokenType::Bang => self.emit_op(OpCode::Not),
+            TokenType::Minus => self.emit_op(OpCode::Negate),
+            _ => unreachable!("Not a unary operator"),
+        }
+        Ok(())
+    }
+
+    fn visit_variable_expr(&mut self, expr: Rc<Expr>) -> Result<(), LoxRuntimeError> {
+        let variable = unwrap_expr!(expr, Variable);
+
+        self.token = variable.name().clone();
+        self.named_variable(variable.name().lexeme(), false)
+    }
+}
+
+impl stmt::Visitor<()> for Compiler {
+    fn visit_block_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let block = unwrap_stmt!(stmt, Block);
+        self.block(block.statements())
+    }
+
+    fn visit_break_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Break);
+
+        self.token = inner.keyword().clone();
+        let jump = self.exit_loop_body()?;
+        self.current_mut().loops.last_mut().expect("Resolver checks loops").breaks.push(jump);
+        Ok(())
+    }
+
+    fn visit_class_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let class = unwrap_stmt!(stmt, Class);
+
+        if let Some(superclass) = class.superclass() {
+            self.expression(superclass)?;
+            self.token = class.name().clone();
+            self.emit_op(OpCode::CheckSuperclass);
+        }
+
+        self.token = class.name().clone();
+        let name = self.identifier_constant(class.name().lexeme())?;
+        self.emit_op_u16(OpCode::Class, name);
+        self.define_variable(class.name())?;
+
+        if let Some(superclass) = class.superclass() {
+            self.begin_scope();
+            self.expression(superclass)?;
+            self.add_local("super")?;
+
+            self.token = class.name().clone();
+            self.named_variable(class.name().lexeme(), false)?;
+            self.emit_op(OpCode::Inherit);
+        }
+
+        self.token = class.name().clone();
+        self.named_variable(class.name().lexeme(), false)?;
+        for method in class.methods() {
+            let function_type = match method.name().lexeme() {
+                "init" => FunctionType::Initializer,
+                _ => FunctionType::Method,
+            };
+            self.function(method, function_type)?;
+            let name = self.identifier_constant(method.name().lexeme())?;
+            self.emit_op_u16(OpCode::Method, name);
+        }
+        self.emit_op(OpCode::Pop);
+
+        if class.superclass().is_some() {
+            self.end_scope();
+        }
+        Ok(())
+    }
+
+    fn visit_continue_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Continue);
+
+        self.token = inner.keyword().clone();
+        let jump = self.exit_loop_body()?;
+        self.current_mut().loops.last_mut().expect("Resolver checks loops").continues.push(jump);
+        Ok(())
+    }
+
+    fn visit_expression_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let expression = unwrap_stmt!(stmt, Expression);
+
+        self.expression(expression.expression())?;
+        self.emit_op(OpCode::Pop);
+        Ok(())
+    }
+
+    fn visit_function_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Function);
+
+        // A local function is in scope in its own body, for recursion.
+        if self.current().scope_depth > 0 {
+            self.add_local(inner.name().lexeme())?;
+            return self.function(inner, FunctionType::Function);
+        }
+
+        self.function(inner, FunctionType::Function)?;
+        self.define_variable(inner.name())
+    }
+
+    fn visit_if_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, If);
+
+        self.expression(inner.condition())?;
+
+        let then_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.emit_op(OpCode::Pop);
+        self.statement(inner.then_branch())?;
+
+        let else_jump = self.emit_jump(OpCode::Jump);
+        self.patch_jump(then_jump)?;
+        self.emit_op(OpCode::Pop);
+
+        if let Some(else_branch) = inner.else_branch() {
+            self.statement(else_branch)?;
+        }
+        self.patch_jump(else_jump)
+    }
+
+    fn visit_import_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let import = unwrap_stmt!(stmt, Import);
+
+        self.token = import.keyword().clone();
+        let path = self.identifier_constant(import.path())?;
+        let name = self.identifier_constant(import.name().lexeme())?;
+        self.emit_op_u16(OpCode::Import, path);
+        self.emit_u16(name);
+        self.define_variable(import.name())
+    }
+
+    fn visit_print_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let print = unwrap_stmt!(stmt, Print);
+
+        self.expression(print.expression())?;
+        self.emit_op(OpCode::Print);
+        Ok(())
+    }
+
+    fn visit_return_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Return);
+
+        match inner.value() {
+            Some(value) => self.expression(value)?,
+            None if self.current().function_type == FunctionType::Initializer =>
+                self.emit_op_u16(OpCode::GetLocal, 0),
+            None => self.emit_op(OpCode::Nil),
+        }
+
+        self.token = inner.keyword().clone();
+        if self.current().tries.is_empty() {
+            self.emit_op(OpCode::Return);
+            return Ok(());
+        }
+
+        // The `finally` blocks run after the return value is evaluated.
+        self.emit_op(OpCode::Stash);
+        self.unwind_tries(0)?;
+        self.emit_op(OpCode::Unstash);
+        self.emit_op(OpCode::Return);
+        Ok(())
+    }
+
+    fn visit_throw_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Throw);
+
+        self.expression(inner.value())?;
+        self.token = inner.keyword().clone();
+        self.emit_op(OpCode::Throw);
+        Ok(())
+    }
+
+    // Layout, with both clauses:
+    //
+    //     Try catch            ; the handler
+    //     <body>
+    //     EndTry
+    //     Jump end
+    //   catch:                 ; the exception is on the stack
+    //     Try finally_throw
+    //     <catch body>
+    //     EndTry
+    //     Pop                  ; the catch variable
+    //     Jump end
+    //   finally_throw:         ; the exception is on the stack
+    //     <finally body>
+    //     GetLocal; Rethrow    ; rethrows the exception
+    //   end:
+    //     <finally body>
+    fn visit_try_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, Try);
+
+        let depth = self.current().locals.len();
+        let finally_body = inner.finally_body().clone();
+        let try_ = Try { locals: depth, finally_body: finally_body.clone() };
+
+        let handler = self.emit_jump(OpCode::Try);
+        self.emit_u16(depth as u16);
+        self.current_mut().tries.push(try_.clone());
+        self.block(inner.body())?;
+        self.current_mut().tries.pop();
+        self.emit_op(OpCode::EndTry);
+
+        let mut end_jumps = vec![self.emit_jump(OpCode::Jump)];
+        self.patch_jump(handler)?;
+
+        if let (Some(name), Some(catch_body)) = (inner.catch_name(), inner.catch_body()) {
+            self.begin_scope();
+            self.add_local(name.lexeme())?;
+
+            let finally_handler = match finally_body {
+                Some(_) => {
+                    let handler = self.emit_jump(OpCode::Try);
+                    self.emit_u16(depth as u16);
+                    self.current_mut().tries.push(try_.clone());
+                    Some(handler)
+                }
+                None => None,
+            };
+
+            for stmt in catch_body {
+                self.statement(stmt)?;
+            }
+
+            if finally_handler.is_some() {
+                self.current_mut().tries.pop();
+                self.emit_op(OpCode::EndTry);
+            }
+            self.end_scope();
+            end_jumps.push(self.emit_jump(OpCode::Jump));
+
+            if let Some(handler) = finally_handler {
+                self.patch_jump(handler)?;
+            }
+        }
+
+        if let Some(finally_body) = &finally_body {
+            self.begin_scope();
+            self.add_local("")?;
+            for stmt in finally_body {
+                self.statement(stmt)?;
+            }
+            self.emit_op_u16(OpCode::GetLocal, depth as u16);
+            self.emit_op(OpCode::Rethrow);
+            self.end_scope();
+        }
+
+        for jump in end_jumps {
+            self.patch_jump(jump)?;
+        }
+
+        if let Some(finally_body) = &finally_body {
+            self.block(finally_body)?;
+        }
+        Ok(())
+    }
+
+    fn visit_var_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let var = unwrap_stmt!(stmt, Var);
+
+        match var.initializer() {
+            Some(initializer) => self.expression(initializer)?,
+            None => self.emit_op(OpCode::Nil),
+        }
+        self.token = var.name().clone();
+        self.define_variable(var.name())
+    }
+
+    fn visit_while_stmt(&mut self, stmt: Rc<Stmt>) -> Result<(), LoxRuntimeError> {
+        let inner = unwrap_stmt!(stmt, While);
+
+        let loop_start = self.current().function.chunk.code().len();
+        self.expression(inner.condition())?;
+
+        let exit_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.emit_op(OpCode::Pop);
+
+        let state = self.current_mut();
+        let (locals, tries) = (state.locals.len(), state.tries.len());
+        state.loops.push(Loop { locals, tries, breaks: vec![], continues: vec![] });
+
+        self.statement(inner.body())?;
+
+        let current_loop = self.current_mut().loops.pop().expect("Loop state");
+        // Desugared `for` loop: `continue` jumps to the increment clause.
+        for jump in current_loop.continues {
+            self.patch_jump(jump)?;
+        }
+        if let Some(increment) = inner.increment() {
+            self.expression(increment)?;
+            self.emit_op(OpCode::Pop);
+        }
+        self.emit_loop(loop_start)?;
+
+        self.patch_jump(exit_jump)?;
+        self.emit_op(OpCode::Pop);
+        for jump in current_loop.breaks {
+            self.patch_jump(jump)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,944 @@
+/* Date Created: 17/10/2026. */
+
+//! The stack-based virtual machine. Chapter 15: [A Virtual Machine](https://craftinginterpreters.com/a-virtual-machine.html)
+//! onwards.
+//!
+//! The public interface follows [`crate::interpreter::Interpreter`]: output
+//! and errors go to separate `Writable`s, and after an uncaught runtime error 
+//! execution carries on with the next top-level statement.
+
+use std::io::Write;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::fs::{canonicalize, read_to_string};
+
+use super::chunk::OpCode;
+use super::compiler::Compiler;
+use super::native::{Native, to_index, check_key};
+use super::value::{Value, Globals, Table, ObjFunction, ObjClosure, ObjUpvalue, ObjClass,
+    ObjInstance, ObjBoundMethod, ObjModule};
+use crate::interpreter::{Interpreter, Writable};
+use crate::lox_error::LoxError;
+use crate::lox_error_helper::{error, sys_error};
+use crate::lox_map::LoxMap;
+use crate::stmt::Stmt;
+use crate::token::Token;
+use crate::scanner::Scanner;
+use crate::parser::Parser;
+use crate::resolver::Resolver;
+use crate::stack_trace::{stack_trace, FrameKind, StackFrame};
+
+/// The default limit of call frames. jlox has no limit, clox has 64.
+pub const FRAMES_MAX: usize = 4096;
+
+struct CallFrame {
+    closure: Rc<ObjClosure>,
+    ip: usize,
+    // Stack index of slot zero.
+    slots: usize,
+    // The value being returned while `finally` blocks run.
+    stash: Option<Value>,
+    // The error the exception caught last in this frame reports, for 
+    // `finally` blocks to rethrow.
+    rethrow: Option<LoxError>,
+    // Set for the top-level function of a module: its canonical path, its name 
+    // and its path as imported.
+    module: Option<(PathBuf, String, String)>,
+}
+
+// An active `try` statement.
+struct Handler {
+    frames: usize,
+    catch_ip: usize,
+    stack: usize,
+}
+
+// Why execution stops: a native runtime error, or a thrown value and the 
+// error it reports if nothing catches it.
+enum Unwind {
+    Error(LoxError),
+    Throw(Value, Box<LoxError>),
+}
+
+impl From<LoxError> for Unwind {
+    fn from(err: LoxError) -> Self {
+        Unwind::Error(err)
+    }
+}
+
+pub struct Vm {
+    output: Box<dyn Writable>,
+    error_output: Box<dyn Writable>,
+    stack: Vec<Value>,
+    frames: Vec<CallFrame>,
+    handlers: Vec<Handler>,
+    // Sorted by stack slot.
+    open_upvalues: Vec<Rc<RefCell<ObjUpvalue>>>,
+    globals: Globals,
+    modules: HashMap<PathBuf, Rc<ObjModule>>,
+    importing: Vec<PathBuf>,
+    // The limit of frames, FRAMES_MAX by default.
+    max_frames: usize,
+}
+
+impl Vm {
+    pub fn new<W: Writable + 'static>(output: W) -> Self {
+        Vm {
+            output: Box::new(output),
+            error_output: Box::new(std::io::stderr()),
+            stack: Vec::with_capacity(256),
+            frames: Vec::with_capacity(64),
+            handlers: vec![],
+            open_upvalues: vec![],
+            globals: Self::new_globals(),
+            modules: HashMap::new(),
+            importing: vec![],
+            max_frames: FRAMES_MAX,
+        }
+    }
+
+    /// See `Interpreter::with_error_output()`.
+    #[allow(dead_code)]
+    pub fn with_error_output<W: Writable + 'static>(mut self, error_output: W) -> Self {
+        self.error_output = Box::new(error_output);
+        self
+    }
+
+    #[allow(dead_code)]
+    // Used by tests.
+    pub fn get_output(&self) -> &dyn Writable {
+        self.output.as_ref()
+    }
+
+    #[allow(dead_code)]
+    // Used by tests.
+    pub fn get_error_output(&self) -> &dyn Writable {
+        self.error_output.as_ref()
+    }
+
+    /// See `Interpreter::set_max_call_depth()`. The VM does not recurse on 
+    /// the Rust stack, so the limit can be much deeper.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.max_frames = depth;
+    }
+
+    /// See `Interpreter::set_script_path()`.
+    pub fn set_script_path(&mut self, path: &str) {
+        self.importing.clear();
+        if let Ok(path) = canonicalize(path) {
+            self.importing.push(path);
+        }
+    }
+
+    fn new_globals() -> Globals {
+        let globals: Globals = Rc::new(RefCell::new(Table::default()));
+        globals.borrow_mut().insert(Rc::from("clock"), Value::Native(Rc::new(Native::Clock)));
+        globals
+    }
+
+    fn write_output(&mut self, value: &str) {
+        writeln!(self.output, "{}", value).expect("Failed to write output");
+    }
+
+    /// See `Interpreter::report_error()`.
+    pub fn report_error(&mut self, report: &str) {
+        write!(self.error_output, "{}", report).expect("Failed to write error output");
+    }
+
+    /// Compiles and runs all statements, captures all errors. Errors are
+    /// returned the same way as `Interpreter::interpret()`.
+    pub fn interpret(&mut self, statements: &[Rc<Stmt>]) -> Result<(), LoxError> {
+        let function = Compiler::compile(statements)?;
+
+        let closure = Rc::new(ObjClosure {
+            function,
+            upvalues: vec![],
+            globals: Rc::clone(&self.globals),
+            source: None,
+        });
+        self.stack.push(Value::Closure(Rc::clone(&closure)));
+        self.frames.push(CallFrame { closure, ip: 0, slots: 0, stash: None, rethrow: None, module: None });
+
+        let mut errors: Vec<LoxError> = vec![];
+        while let Err(unwind) = self.run() {
+            let err = match unwind {
+                Unwind::Error(err) => self.with_stack_trace(err, None),
+                Unwind::Throw(_, err) => *err,
+            };
+            errors.push(err);
+
+            if !self.recover() {
+                break;
+            }
+        }
+
+        self.stack.clear();
+        self.frames.clear();
+        self.handlers.clear();
+        self.open_upvalues.clear();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(LoxError::from_errors(errors))
+        }
+    }
+
+    // After an uncaught error: unwinds to the top-level script and carries on
+    // with its next statement. Returns false if there is none.
+    fn recover(&mut self) -> bool {
+        while self.frames.len() > 1 {
+            self.pop_frame();
+        }
+        self.handlers.clear();
+        self.close_upvalues(1);
+        self.stack.truncate(1);
+
+        let frame = &mut self.frames[0];
+        frame.stash = None;
+        frame.rethrow = None;
+        match frame.closure.function.statements.iter().find(|&&offset| offset >= frame.ip) {
+            Some(&offset) => {
+                frame.ip = offset;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // The same stack trace as the tree-walker's, see ./src/stack_trace.rs. 
+    // `native` is the native function the error happened in.
+    fn with_stack_trace(&self, err: LoxError, native: Option<&str>) -> LoxError {
+        if !err.trace().is_empty() {
+            return err;
+        }
+
+        let mut frames: Vec<StackFrame> = self.frames.iter()
+            .map(|frame| {
+                let function = &frame.closure.function;
+                let kind = match &frame.module {
+                    Some((_, _, path)) => FrameKind::Module(path.clone()),
+                    None if function.name.is_empty() => FrameKind::Script,
+                    None => FrameKind::Function(function.name.clone()),
+                };
+                // The ip is past the call instruction.
+                let line = if frame.ip > 0 { function.chunk.token_at(frame.ip - 1).line() } else { 0 };
+                StackFrame::new(kind, line)
+            })
+            .collect();
+        if let Some(name) = native {
+            frames.push(StackFrame::native(name));
+        }
+
+        // An error with no line, such as a rethrown `Error` instance, is in the 
+        // innermost frame's current line.
+        let line = match err.line() {
+            0 => frames.last().map_or(0, |frame| frame.line()),
+            line => line,
+        };
+        let trace = stack_trace(&frames, line);
+        let err = err.with_trace(trace);
+        // Natives have no frame: the error is in the innermost frame's source.
+        match self.frames.last().and_then(|frame| frame.closure.source.as_ref()) {
+            Some(path) => err.with_source_path(path),
+            None => err,
+        }
+    }
+
+    fn pop_frame(&mut self) -> CallFrame {
+        let frame = self.frames.pop().expect("Call frame");
+        if frame.module.is_some() {
+            self.importing.pop();
+        }
+        frame
+    }
+
+    // Runs until the top-level script returns, or an error is not caught.
+    fn run(&mut self) -> Result<(), Unwind> {
+        loop {
+            match self.execute() {
+                Ok(()) => return Ok(()),
+                Err(unwind) => self.catch(unwind)?,
+            }
+        }
+    }
+
+    // Passes `unwind` to the innermost `catch` clause, or `finally` block.
+    fn catch(&mut self, unwind: Unwind) -> Result<(), Unwind> {
+        let handler = match self.handlers.pop() {
+            Some(handler) => handler,
+            None => return Err(unwind),
+        };
+
+        let (value, err) = match unwind {
+            Unwind::Throw(value, err) => (value, *err),
+            Unwind::Error(err) => (error_instance(&err), self.with_stack_trace(err, None)),
+        };
+
+        while self.frames.len() > handler.frames {
+            self.pop_frame();
+        }
+        self.frames.last_mut().expect("Call frame").rethrow = Some(err);
+        self.close_upvalues(handler.stack);
+        self.stack.truncate(handler.stack);
+        self.stack.push(value);
+        self.frames.last_mut().expect("Call frame").ip = handler.catch_ip;
+        Ok(())
+    }
+
+    fn peek(&self, distance: usize) -> &Value {
+        &self.stack[self.stack.len() - 1 - distance]
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack.pop().expect("Stack underflow")
+    }
+
+    fn execute(&mut self) -> Result<(), Unwind> {
+        let frame = self.frames.last().expect("Call frame");
+        let mut closure = Rc::clone(&frame.closure);
+        let mut ip = frame.ip;
+        let mut slots = frame.slots;
+
+        macro_rules! read_byte {
+            () => {{
+                let byte = closure.function.chunk.code()[ip];
+                ip += 1;
+                byte
+            }};
+        }
+
+        macro_rules! read_u16 {
+            () => {{
+                let code = closure.function.chunk.code();
+                let value = ((code[ip] as usize) << 8) | code[ip + 1] as usize;
+                ip += 2;
+                value
+            }};
+        }
+
+        macro_rules! read_string {
+            () => {{
+                match closure.function.chunk.constant(read_u16!()) {
+                    Value::String(s) => s,
+                    _ => unreachable!("Expected a string constant"),
+                }
+            }};
+        }
+
+        // The token of the current instruction.
+        macro_rules! token {
+            () => {
+                closure.function.chunk.token_at(ip - 1)
+            };
+        }
+
+        // Saves `ip` before anything which may fail, or switch frames.
+        macro_rules! save_ip {
+            () => {
+                self.frames.last_mut().expect("Call frame").ip = ip
+            };
+        }
+
+        macro_rules! fail {
+            ($msg:expr) => {{
+                save_ip!();
+                return Err(Unwind::Error(error(token!(), $msg)));
+            }};
+        }
+
+        macro_rules! try_op {
+            ($result:expr) => {
+                match $result {
+                    Ok(value) => value,
+                    Err(err) => {
+                        save_ip!();
+                        return Err(Unwind::Error(err));
+                    }
+                }
+            };
+        }
+
+        macro_rules! load_frame {
+            () => {{
+                let frame = self.frames.last().expect("Call frame");
+                closure = Rc::clone(&frame.closure);
+                ip = frame.ip;
+                slots = frame.slots;
+            }};
+        }
+
+        macro_rules! number_op {
+            ($op:tt, $variant:ident) => {{
+                match (self.peek(1), self.peek(0)) {
+                    (Value::Number(a), Value::Number(b)) => {
+                        let result = Value::$variant(*a $op *b);
+                        self.stack.pop();
+                        *self.stack.last_mut().expect("Stack underflow") = result;
+                    }
+                    _ => fail!("Operand must be a number."),
+                }
+            }};
+        }
+
+        loop {
+            match OpCode::from_byte(read_byte!()) {
+                OpCode::Constant => {
+                    let value = closure.function.chunk.constant(read_u16!()).clone();
+                    self.stack.push(value);
+                }
+                OpCode::Nil => self.stack.push(Value::Nil),
+                OpCode::True => self.stack.push(Value::Boolean(true)),
+                OpCode::False => self.stack.push(Value::Boolean(false)),
+                OpCode::Pop => {
+                    self.stack.pop();
+                }
+                OpCode::PopN => {
+                    let count = read_u16!();
+                    let len = self.stack.len() - count;
+                    self.close_upvalues(len);
+                    self.stack.truncate(len);
+                }
+                OpCode::GetLocal => {
+                    let slot = read_u16!();
+                    let value = self.stack[slots + slot].clone();
+                    self.stack.push(value);
+                }
+                OpCode::SetLocal => {
+                    let slot = read_u16!();
+                    self.stack[slots + slot] = self.peek(0).clone();
+                }
+                OpCode::GetGlobal => {
+                    let name = read_string!();
+                    let value = closure.globals.borrow().get(name).cloned();
+                    match value {
+                        Some(value) => self.stack.push(value),
+                        None => fail!(&format!("Undefined variable '{}'.", name)),
+                    }
+                }
+                OpCode::DefineGlobal => {
+                    let name = read_string!();
+                    let value = self.pop();
+                    closure.globals.borrow_mut().insert(Rc::clone(name), value);
+                }
+                OpCode::SetGlobal => {
+                    let name = read_string!();
+                    let value = self.peek(0).clone();
+                    match closure.globals.borrow_mut().get_mut(name) {
+                        Some(variable) => *variable = value,
+                        None => fail!(&format!("Undefined variable '{}'.", name)),
+                    }
+                }
+                OpCode::GetUpvalue => {
+                    let index = read_u16!();
+                    let value = match &*closure.upvalues[index].borrow() {
+                        ObjUpvalue::Open(slot) => self.stack[*slot].clone(),
+                        ObjUpvalue::Closed(value) => value.clone(),
+                    };
+                    self.stack.push(value);
+                }
+                OpCode::SetUpvalue => {
+                    let index = read_u16!();
+                    let value = self.peek(0).clone();
+                    let mut upvalue = closure.upvalues[index].borrow_mut();
+                    match &mut *upvalue {
+                        ObjUpvalue::Open(slot) => self.stack[*slot] = value,
+                        ObjUpvalue::Closed(closed) => *closed = value,
+                    }
+                }
+                OpCode::GetProperty => {
+                    let name = read_string!();
+                    let object = self.pop();
+                    let value = try_op!(get_property(&object, name, token!()));
+                    self.stack.push(value);
+                }
+                OpCode::CheckInstance => {
+                    if !matches!(self.peek(0), Value::Instance(_)) {
+                        fail!("Only instances have fields.");
+                    }
+                }
+                OpCode::SetProperty => {
+                    let name = read_string!();
+                    let value = self.pop();
+                    if let Value::Instance(instance) = self.pop() {
+                        instance.borrow_mut().fields.insert(Rc::clone(name), value.clone());
+                    }
+                    self.stack.push(value);
+                }
+                OpCode::GetSuper => {
+                    let name = read_string!();
+                    let superclass = self.pop();
+                    let receiver = self.pop();
+                    let method = match &superclass {
+                        Value::Class(class) => class.find_method(name),
+                        _ => None,
+                    };
+                    match method {
+                        Some(method) => self.stack.push(Value::BoundMethod(
+                            Rc::new(ObjBoundMethod { receiver, method }))),
+                        None => fail!(&format!("Undefined property '{}'.", name)),
+                    }
+                }
+                OpCode::GetIndex => {
+                    let index = self.pop();
+                    let object = self.pop();
+                    let value = match &object {
+                        Value::List(list) => {
+                            let list = list.borrow();
+                            let i = try_op!(to_index(token!(), &index, list.len()));
+                            list[i].clone()
+                        }
+                        Value::Map(map) => {
+                            try_op!(check_key(token!(), &index));
+                            match map.borrow().get(&index) {
+                                Some(value) => value,
+                                None => fail!(&format!("Undefined key '{}'.", index.stringify())),
+                            }
+                        }
+                        _ => fail!("Only lists and maps can be indexed."),
+                    };
+                    self.stack.push(value);
+                }
+                OpCode::CheckIndex => {
+                    match self.peek(1) {
+                        Value::List(_) => {}
+                        Value::Map(_) => try_op!(check_key(token!(), self.peek(0))),
+                        _ => fail!("Only lists and maps can be indexed."),
+                    }
+                }
+                OpCode::SetIndex => {
+                    let value = self.pop();
+                    let index = self.pop();
+                    match self.pop() {
+                        Value::List(list) => {
+                            let mut list = list.borrow_mut();
+                            let i = try_op!(to_index(token!(), &index, list.len()));
+                            list[i] = value.clone();
+                        }
+                        Value::Map(map) => {
+                            map.borrow_mut().insert(index, value.clone());
+                        }
+                        _ => unreachable!("Checked by OpCode::CheckIndex"),
+                    }
+                    self.stack.push(value);
+                }
+                OpCode::CheckKey => try_op!(check_key(token!(), self.peek(0))),
+                OpCode::Equal => {
+                    let b = self.pop();
+                    let a = self.pop();
+                    self.stack.push(Value::Boolean(a == b));
+                }
+                OpCode::NotEqual => {
+                    let b = self.pop();
+                    let a = self.pop();
+                    self.stack.push(Value::Boolean(a != b));
+                }
+                OpCode::Greater => number_op!(>, Boolean),
+                OpCode::GreaterEqual => number_op!(>=, Boolean),
+                OpCode::Less => number_op!(<, Boolean),
+                OpCode::LessEqual => number_op!(<=, Boolean),
+                OpCode::Add => {
+                    let result = match (self.peek(1), self.peek(0)) {
+                        (Value::Number(a), Value::Number(b)) => Value::Number(a + b),
+                        (Value::String(a), Value::String(b)) => {
+                            let mut s = String::with_capacity(a.len() + b.len());
+                            s.push_str(a);
+                            s.push_str(b);
+                            Value::String(Rc::from(s))
+                        }
+                        _ => fail!("Operands must be two numbers or two strings."),
+                    };
+                    self.stack.pop();
+                    *self.stack.last_mut().expect("Stack underflow") = result;
+                }
+                OpCode::Subtract => number_op!(-, Number),
+                OpCode::Multiply => number_op!(*, Number),
+                OpCode::Divide => number_op!(/, Number),
+                OpCode::Not => {
+                    let value = self.pop();
+                    self.stack.push(Value::Boolean(!value.is_truthy()));
+                }
+                OpCode::Negate => match self.pop() {
+                    Value::Number(n) => self.stack.push(Value::Number(-n)),
+                    _ => fail!("Operand must be a number."),
+                },
+                OpCode::Print => {
+                    let value = self.pop();
+                    self.write_output(&value.stringify());
+                }
+                OpCode::Jump => {
+                    let offset = read_u16!();
+                    ip += offset;
+                }
+                OpCode::JumpIfFalse => {
+                    let offset = read_u16!();
+                    if !self.peek(0).is_truthy() {
+                        ip += offset;
+                    }
+                }
+                OpCode::Loop => {
+                    let offset = read_u16!();
+                    ip -= offset;
+                }
+                OpCode::Call => {
+                    let arg_count = read_byte!() as usize;
+                    save_ip!();
+                    let callee = self.peek(arg_count).clone();
+                    try_op!(self.call_value(callee, arg_count, token!()));
+                    load_frame!();
+                }
+                OpCode::Invoke => {
+                    let name = read_string!();
+                    let arg_count = read_byte!() as usize;
+                    let name_token = read_u16!();
+                    save_ip!();
+                    let name_token = closure.function.chunk.token(name_token);
+                    try_op!(self.invoke(name, arg_count, name_token, token!()));
+                    load_frame!();
+                }
+                OpCode::Closure => {
+                    let function = match closure.function.chunk.constant(read_u16!()) {
+                        Value::Function(function) => Rc::clone(function),
+                        _ => unreachable!("Expected a function constant"),
+                    };
+
+                    let mut upvalues = Vec::with_capacity(function.upvalue_count);
+                    for _ in 0..function.upvalue_count {
+                        let is_local = read_byte!() == 1;
+                        let index = read_u16!();
+                        upvalues.push(if is_local {
+                            self.capture_upvalue(slots + index)
+                        } else {
+                            Rc::clone(&closure.upvalues[index])
+                        });
+                    }
+
+                    self.stack.push(Value::Closure(Rc::new(ObjClosure {
+                        function,
+                        upvalues,
+                        globals: Rc::clone(&closure.globals),
+                        source: closure.source.clone(),
+                    })));
+                }
+                OpCode::CloseUpvalue => {
+                    self.close_upvalues(self.stack.len() - 1);
+                    self.stack.pop();
+                }
+                OpCode::Stash => {
+                    let value = self.pop();
+                    self.frames.last_mut().expect("Call frame").stash = Some(value);
+                }
+                OpCode::Unstash => {
+                    let value = self.frames.last_mut().expect("Call frame").stash.take();
+                    self.stack.push(value.unwrap_or(Value::Nil));
+                }
+                OpCode::Return => {
+                    let mut result = self.pop();
+                    self.close_upvalues(slots);
+                    let frame = self.pop_frame();
+
+                    if self.frames.is_empty() {
+                        self.stack.truncate(slots);
+                        return Ok(());
+                    }
+
+                    // The top-level function of a module returns the module.
+                    if let Some((path, name, _)) = frame.module {
+                        let module = Rc::new(ObjModule { name, globals: Rc::clone(&closure.globals) });
+                        self.modules.insert(path, Rc::clone(&module));
+                        result = Value::Module(module);
+                    }
+
+                    self.stack.truncate(slots);
+                    self.stack.push(result);
+                    load_frame!();
+                }
+                OpCode::Class => {
+                    let name = read_string!();
+                    self.stack.push(Value::Class(Rc::new(ObjClass::new(name))));
+                }
+                OpCode::CheckSuperclass => {
+                    if !matches!(self.pop(), Value::Class(_)) {
+                        fail!("Superclass must be a class.");
+                    }
+                }
+                OpCode::Inherit => {
+                    if let (Value::Class(subclass), Value::Class(superclass)) = (self.pop(), self.peek(0)) {
+                        let methods = superclass.methods.borrow().clone();
+                        subclass.methods.borrow_mut().extend(methods);
+                    }
+                }
+                OpCode::Method => {
+                    let name = read_string!();
+                    if let (Value::Closure(method), Value::Class(class)) = (self.pop(), self.peek(0)) {
+                        class.methods.borrow_mut().insert(Rc::clone(name), method);
+                    }
+                }
+                OpCode::List => {
+                    let count = read_u16!();
+                    let elements = self.stack.split_off(self.stack.len() - count);
+                    self.stack.push(Value::List(Rc::new(RefCell::new(elements))));
+                }
+                OpCode::Map => {
+                    let count = read_u16!();
+                    let entries = self.stack.split_off(self.stack.len() - count * 2);
+                    let mut map = LoxMap::new();
+                    let mut entries = entries.into_iter();
+                    while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+                        map.insert(key, value);
+                    }
+                    self.stack.push(Value::Map(Rc::new(RefCell::new(map))));
+                }
+                OpCode::Throw => {
+                    let value = self.pop();
+                    save_ip!();
+                    let err = self.with_stack_trace(uncaught(&value, token!()), None);
+                    return Err(Unwind::Throw(value, Box::new(err)));
+                }
+                OpCode::Rethrow => {
+                    let value = self.pop();
+                    save_ip!();
+                    let err = match self.frames.last_mut().expect("Call frame").rethrow.take() {
+                        Some(err) => err,
+                        None => self.with_stack_trace(uncaught(&value, token!()), None),
+                    };
+                    return Err(Unwind::Throw(value, Box::new(err)));
+                }
+                OpCode::Try => {
+                    let offset = read_u16!();
+                    let catch_ip = ip + offset;
+                    let depth = read_u16!();
+                    self.handlers.push(Handler {
+                        frames: self.frames.len(),
+                        catch_ip,
+                        stack: slots + depth,
+                    });
+                }
+                OpCode::EndTry => {
+                    self.handlers.pop();
+                }
+                OpCode::Import => {
+                    let path = read_string!();
+                    let name = read_string!();
+                    save_ip!();
+                    try_op!(self.import(path, name, token!()));
+                    load_frame!();
+                }
+            }
+        }
+    }
+
+    fn call_value(&mut self, callee: Value, arg_count: usize, paren: &Token) -> Result<(), LoxError> {
+        let callee_slot = self.stack.len() - arg_count - 1;
+
+        match callee {
+            Value::Closure(closure) => self.call(closure, arg_count, paren),
+            Value::BoundMethod(bound) => {
+                self.stack[callee_slot] = bound.receiver.clone();
+                self.call(Rc::clone(&bound.method), arg_count, paren)
+            }
+            Value::Class(class) => {
+                let initializer = class.find_method("init");
+                self.stack[callee_slot] = Value::Instance(Rc::new(RefCell::new(
+                    ObjInstance { class, fields: Table::default() })));
+
+                match initializer {
+                    Some(initializer) => self.call(initializer, arg_count, paren),
+                    None if arg_count != 0 => Err(error(paren,
+                        &format!("Expected 0 arguments but got {}.", arg_count))),
+                    None => Ok(()),
+                }
+            }
+            Value::Native(native) => {
+                if arg_count != native.arity() {
+                    return Err(error(paren, &format!(
+                        "Expected {} arguments but got {}.", native.arity(), arg_count)));
+                }
+                let result = native.call(&self.stack[callee_slot + 1..])
+                    .map_err(|err| self.with_stack_trace(err, Some(native.name())))?;
+                self.stack.truncate(callee_slot);
+                self.stack.push(result);
+                Ok(())
+            }
+            _ => Err(error(paren, "Can only call functions and classes.")),
+        }
+    }
+
+    fn call(&mut self, closure: Rc<ObjClosure>, arg_count: usize, paren: &Token) -> Result<(), LoxError> {
+        if arg_count != closure.function.arity {
+            return Err(error(paren, &format!(
+                "Expected {} arguments but got {}.", closure.function.arity, arg_count)));
+        }
+        if self.frames.len() >= self.max_frames {
+            return Err(error(paren, "Stack overflow."));
+        }
+
+        self.frames.push(CallFrame {
+            closure,
+            ip: 0,
+            slots: self.stack.len() - arg_count - 1,
+            stash: None,
+            rethrow: None,
+            module: None,
+        });
+        Ok(())
+    }
+
+    fn invoke(&mut self, name: &str, arg_count: usize,
+        name_token: &Token, paren: &Token) -> Result<(), LoxError> {
+
+        let receiver_slot = self.stack.len() - arg_count - 1;
+        let receiver = self.stack[receiver_slot].clone();
+
+        if let Value::Instance(instance) = &receiver {
+            let instance = instance.borrow();
+            if let Some(field) = instance.fields.get(name) {
+                let field = field.clone();
+                drop(instance);
+                self.stack[receiver_slot] = field.clone();
+                return self.call_value(field, arg_count, paren);
+            }
+
+            return match instance.class.find_method(name) {
+                Some(method) => {
+                    drop(instance);
+                    self.call(method, arg_count, paren)
+                }
+                None => Err(error(name_token, &format!("Undefined property '{}'.", name))),
+            };
+        }
+
+        let callee = get_property(&receiver, name, name_token)?;
+        self.stack[receiver_slot] = callee.clone();
+        self.call_value(callee, arg_count, paren)
+    }
+
+    fn capture_upvalue(&mut self, slot: usize) -> Rc<RefCell<ObjUpvalue>> {
+        let position = self.open_upvalues.iter().rposition(|upvalue| {
+            matches!(*upvalue.borrow(), ObjUpvalue::Open(s) if s <= slot)
+        });
+
+        if let Some(i) = position
+            && matches!(*self.open_upvalues[i].borrow(), ObjUpvalue::Open(s) if s == slot) {
+            return Rc::clone(&self.open_upvalues[i]);
+        }
+
+        let upvalue = Rc::new(RefCell::new(ObjUpvalue::Open(slot)));
+        let at = position.map_or(0, |i| i + 1);
+        self.open_upvalues.insert(at, Rc::clone(&upvalue));
+        upvalue
+    }
+
+    // Closes the upvalues of stack slots from `last` upwards.
+    fn close_upvalues(&mut self, last: usize) {
+        while let Some(upvalue) = self.open_upvalues.last() {
+            let slot = match *upvalue.borrow() {
+                ObjUpvalue::Open(slot) => slot,
+                ObjUpvalue::Closed(_) => unreachable!("Open upvalues only"),
+            };
+            if slot < last {
+                break;
+            }
+            *upvalue.borrow_mut() = ObjUpvalue::Closed(self.stack[slot].clone());
+            self.open_upvalues.pop();
+        }
+    }
+
+    // See `Interpreter::import_module()`.
+    fn import(&mut self, path: &str, name: &str, keyword: &Token) -> Result<(), LoxError> {
+        let base: PathBuf = self.importing.last()
+            .and_then(|script| script.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let canonical = canonicalize(base.join(path)).map_err(|_| error(
+            keyword, &format!("Can't open module '{}'.", path)))?;
+
+        if let Some(module) = self.modules.get(&canonical) {
+            self.stack.push(Value::Module(Rc::clone(module)));
+            return Ok(());
+        }
+
+        if let Some(start) = self.importing.iter().position(|script| *script == canonical) {
+            let cycle: Vec<String> = self.importing[start..]
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|script| script.file_name().unwrap_or_default().to_string_lossy().to_string())
+                .collect();
+            return Err(error(keyword, &format!("Import cycle: {}.", cycle.join(" -> "))));
+        }
+
+        let source = read_to_string(&canonical).map_err(|_| error(
+            keyword, &format!("Can't open module '{}'.", path)))?;
+        let function = load_module(&source).map_err(|err| error(
+            keyword, &format!("Error in module '{}': {}", path, err)))?;
+
+        let closure = Rc::new(ObjClosure { function, upvalues: vec![], globals: Self::new_globals(),
+            source: Some(Rc::from(canonical.as_path())) });
+        self.stack.push(Value::Closure(Rc::clone(&closure)));
+        self.frames.push(CallFrame {
+            closure,
+            ip: 0,
+            slots: self.stack.len() - 1,
+            stash: None,
+            rethrow: None,
+            module: Some((canonical.clone(), name.to_string(), path.to_string())),
+        });
+        self.importing.push(canonical);
+        Ok(())
+    }
+}
+
+// The static checks are the tree-walker's, so that both report the same errors.
+fn load_module(source: &str) -> Result<Rc<ObjFunction>, LoxError> {
+    let tokens = Scanner::new(source).scan_tokens()?;
+    let statements = Parser::new(&tokens).parse()?;
+    let mut interpreter = Interpreter::new(std::io::sink());
+    Resolver::new(&mut interpreter).resolve(&statements)?;
+    Compiler::compile(&statements)
+}
+
+fn get_property(object: &Value, name: &str, token: &Token) -> Result<Value, LoxError> {
+    let undefined = || error(token, &format!("Undefined property '{}'.", name));
+
+    match object {
+        Value::Instance(instance) => {
+            let instance = instance.borrow();
+            if let Some(value) = instance.fields.get(name) {
+                return Ok(value.clone());
+            }
+            let method = instance.class.find_method(name).ok_or_else(undefined)?;
+            Ok(Value::BoundMethod(Rc::new(ObjBoundMethod { receiver: object.clone(), method })))
+        }
+        Value::List(list) => Native::list_method(list, token),
+        Value::Map(map) => Native::map_method(map, token),
+        Value::Module(module) => module.globals.borrow().get(name).cloned().ok_or_else(undefined),
+        _ => Err(error(token, "Only instances have properties.")),
+    }
+}
+
+/// See `lox_exception::error_instance()`.
+fn error_instance(err: &LoxError) -> Value {
+    let mut fields = Table::default();
+    fields.insert(Rc::from("message"), Value::String(Rc::from(err.err_msg())));
+    fields.insert(Rc::from("line"), Value::Number(err.line() as f64));
+
+    Value::Instance(Rc::new(RefCell::new(ObjInstance { class: Rc::new(ObjClass::new("Error")), fields })))
+}
+
+/// See `lox_exception::uncaught()`.
+fn uncaught(value: &Value, keyword: &Token) -> LoxError {
+    if let Value::Instance(instance) = value {
+        let instance = instance.borrow();
+        if let (Some(Value::String(message)), Some(Value::Number(line))) =
+            (instance.fields.get("message"), instance.fields.get("line")) {
+            return match *line as usize {
+                0 => sys_error("", message),
+                line => sys_error("", &format!("[line {}] Error: {}", line, message)),
+            };
+        }
+    }
+
+    error(keyword, &format!("Uncaught exception: {}.", value))
+}
@@ -0,0 +1,19 @@
+/* Date Created: 17/10/2026. */
+
+//! Not in the book's jlox part. A second backend, following the book's clox 
+//! part: [`compiler::Compiler`] compiles the [`crate::stmt::Stmt`] syntax tree 
+//! into bytecode [`chunk::Chunk`]s, and [`machine::Vm`] runs them on a stack 
+//! with call frames and upvalues.
+//!
+//! The scanner, the parser and the [`crate::resolver::Resolver`] are shared with 
+//! the tree-walking [`crate::interpreter::Interpreter`]: the VM runs only 
+//! programs which have been resolved successfully, and its output, including 
+//! runtime error messages, is identical to the tree-walker's.
+//!
+//! Run a script on the VM with `cargo run -- --vm ./tests/data/benchmark/fib.lox`.
+
+pub mod chunk;
+pub mod value;
+pub mod native;
+pub mod compiler;
+pub mod machine;
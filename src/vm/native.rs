@@ -0,0 +1,169 @@
+/* Date Created: 17/10/2026. */
+
+//! Native functions of the VM: `clock()`, and the list and map methods of
+//! ./src/lox_list.rs and ./src/lox_map.rs. Error messages are the same as the
+//! tree-walker's.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::value::Value;
+use crate::lox_map::LoxMap;
+use crate::lox_error::LoxError;
+use crate::lox_error_helper::error;
+use crate::token::Token;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListMethod {
+    Push,
+    Pop,
+    Len,
+    Insert,
+    Remove,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MapMethod {
+    Keys,
+    Values,
+    Has,
+    Remove,
+    Len,
+}
+
+#[derive(Debug)]
+pub enum Native {
+    Clock,
+    // The property name token reports runtime errors.
+    List(Rc<RefCell<Vec<Value>>>, ListMethod, Token),
+    Map(Rc<RefCell<LoxMap<Value>>>, MapMethod, Token),
+}
+
+impl Native {
+    /// Binds a list method, see `LoxListMethod::get()`.
+    pub fn list_method(list: &Rc<RefCell<Vec<Value>>>, name: &Token) -> Result<Value, LoxError> {
+        let method = match name.lexeme() {
+            "push" => ListMethod::Push,
+            "pop" => ListMethod::Pop,
+            "len" => ListMethod::Len,
+            "insert" => ListMethod::Insert,
+            "remove" => ListMethod::Remove,
+            _ => return Err(error(name, &format!("Undefined property '{}'.", name.lexeme()))),
+        };
+        Ok(Value::Native(Rc::new(Native::List(Rc::clone(list), method, name.clone()))))
+    }
+
+    /// Binds a map method, see `LoxMapMethod::get()`.
+    pub fn map_method(map: &Rc<RefCell<LoxMap<Value>>>, name: &Token) -> Result<Value, LoxError> {
+        let method = match name.lexeme() {
+            "keys" => MapMethod::Keys,
+            "values" => MapMethod::Values,
+            "has" => MapMethod::Has,
+            "remove" => MapMethod::Remove,
+            "len" => MapMethod::Len,
+            _ => return Err(error(name, &format!("Undefined property '{}'.", name.lexeme()))),
+        };
+        Ok(Value::Native(Rc::new(Native::Map(Rc::clone(map), method, name.clone()))))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Native::Clock => "clock",
+            Native::List(_, _, name) | Native::Map(_, _, name) => name.lexeme(),
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        match self {
+            Native::Clock => 0,
+            Native::List(_, method, _) => match method {
+                ListMethod::Pop | ListMethod::Len => 0,
+                ListMethod::Push | ListMethod::Remove => 1,
+                ListMethod::Insert => 2,
+            },
+            Native::Map(_, method, _) => match method {
+                MapMethod::Keys | MapMethod::Values | MapMethod::Len => 0,
+                MapMethod::Has | MapMethod::Remove => 1,
+            },
+        }
+    }
+
+    pub fn call(&self, arguments: &[Value]) -> Result<Value, LoxError> {
+        match self {
+            Native::Clock => {
+                let since_epoch = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time went backwards");
+                Ok(Value::Number(since_epoch.as_secs() as f64
+                    + since_epoch.subsec_micros() as f64 / 1_000_000.0))
+            }
+            Native::List(list, method, name) => {
+                let mut list = list.borrow_mut();
+                match method {
+                    ListMethod::Push => {
+                        list.push(arguments[0].clone());
+                        Ok(Value::Nil)
+                    }
+                    ListMethod::Pop => list.pop()
+                        .ok_or_else(|| error(name, "Can't pop from an empty list.")),
+                    ListMethod::Len => Ok(Value::Number(list.len() as f64)),
+                    ListMethod::Insert => {
+                        let index = to_index(name, &arguments[0], list.len() + 1)?;
+                        list.insert(index, arguments[1].clone());
+                        Ok(Value::Nil)
+                    }
+                    ListMethod::Remove => {
+                        let index = to_index(name, &arguments[0], list.len())?;
+                        Ok(list.remove(index))
+                    }
+                }
+            }
+            Native::Map(map, method, name) => {
+                let mut map = map.borrow_mut();
+                match method {
+                    MapMethod::Keys => Ok(Value::List(Rc::new(RefCell::new(map.keys())))),
+                    MapMethod::Values => Ok(Value::List(Rc::new(RefCell::new(map.values())))),
+                    MapMethod::Has => {
+                        check_key(name, &arguments[0])?;
+                        Ok(Value::Boolean(map.contains_key(&arguments[0])))
+                    }
+                    MapMethod::Remove => {
+                        check_key(name, &arguments[0])?;
+                        map.remove(&arguments[0]).ok_or_else(|| error(name,
+                            &format!("Undefined key '{}'.", arguments[0].stringify())))
+                    }
+                    MapMethod::Len => Ok(Value::Number(map.len() as f64)),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Native {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<native fn {}>", self.name())
+    }
+}
+
+/// See `LoxListMethod::to_index()`.
+pub fn to_index(token: &Token, index: &Value, end: usize) -> Result<usize, LoxError> {
+    match index {
+        Value::Number(n) if n.fract() != 0.0 =>
+            Err(error(token, "List index must be an integer.")),
+        Value::Number(n) if *n < 0.0 || *n >= end as f64 =>
+            Err(error(token, "List index out of range.")),
+        Value::Number(n) => Ok(*n as usize),
+        _ => Err(error(token, "List index must be a number.")),
+    }
+}
+
+/// See `LoxMap::check_key()`.
+pub fn check_key(token: &Token, key: &Value) -> Result<(), LoxError> {
+    match key {
+        Value::Number(n) if n.is_nan() => Err(error(token, "Map keys can't be NaN.")),
+        Value::Number(_) | Value::String(_) | Value::Boolean(_) => Ok(()),
+        _ => Err(error(token, "Map keys must be strings, numbers or booleans.")),
+    }
+}
@@ -0,0 +1,268 @@
+/* Date Created: 17/10/2026. */
+
+//! The VM's runtime values. Chapter 15: [A Virtual Machine](https://craftinginterpreters.com/a-virtual-machine.html)
+//! onwards, up to Chapter 29: [Superclasses](https://craftinginterpreters.com/superclasses.html).
+//!
+//! Unlike clox, objects are reference counted with `Rc`, the same as the
+//! tree-walker's [`crate::value::Value`]. Printing and equality follow
+//! [`crate::value::Value`] exactly, so that both backends give the same output.
+
+use std::path::Path;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher, BuildHasherDefault};
+use std::fmt;
+
+use super::chunk::Chunk;
+use super::native::Native;
+use crate::lox_map::LoxMap;
+
+/// Hash tables keyed by names: globals, fields and methods. Chapter 20:
+/// [Hash Tables](https://craftinginterpreters.com/hash-tables.html) uses FNV-1a,
+/// which is much faster than the standard library's default for short names.
+pub type Table<V> = HashMap<Rc<str>, V, BuildHasherDefault<FnvHasher>>;
+
+pub type Globals = Rc<RefCell<Table<Value>>>;
+
+#[derive(Default)]
+pub struct FnvHasher(u64);
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut hash = if self.0 == 0 { 0xcbf29ce484222325 } else { self.0 };
+        for byte in bytes {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        self.0 = hash;
+    }
+}
+
+#[derive(Clone)]
+pub enum Value {
+    Number(f64),
+    String(Rc<str>),
+    Boolean(bool),
+    Nil,
+    // Only ever a constant operand of `OpCode::Closure`.
+    Function(Rc<ObjFunction>),
+    Closure(Rc<ObjClosure>),
+    BoundMethod(Rc<ObjBoundMethod>),
+    Class(Rc<ObjClass>),
+    Instance(Rc<RefCell<ObjInstance>>),
+    Native(Rc<Native>),
+    List(Rc<RefCell<Vec<Value>>>),
+    Map(Rc<RefCell<LoxMap<Value>>>),
+    Module(Rc<ObjModule>),
+}
+
+impl Value {
+    // Ruby rule: false and nil are falsey, and everything else is truthy.
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Nil | Value::Boolean(false))
+    }
+
+    /// What `print` shows. See `Interpreter::stringify()`: unlike `Display`,
+    /// it keeps '.0' for numbers.
+    pub fn stringify(&self) -> String {
+        self.nested_to_string(&mut Vec::new(), true)
+    }
+
+    // `stringify()`, or `Display` for lists and maps. `printing` has the lists 
+    // and maps being printed, by address: one which contains itself prints as 
+    // `[...]` or `{...}` there, rather than overflowing the stack.
+    fn nested_to_string(&self, printing: &mut Vec<*const ()>, keep_fraction: bool) -> String {
+        match self {
+            Value::Number(n) if keep_fraction => format!("{:?}", n),
+            Value::List(list) => {
+                let address = Rc::as_ptr(list) as *const ();
+                if printing.contains(&address) {
+                    return "[...]".to_string();
+                }
+                printing.push(address);
+                let elements: Vec<String> = list.borrow()
+                    .iter()
+                    .map(|element| element.nested_to_string(printing, keep_fraction))
+                    .collect();
+                printing.pop();
+                format!("[{}]", elements.join(", "))
+            }
+            Value::Map(map) => {
+                let address = Rc::as_ptr(map) as *const ();
+                if printing.contains(&address) {
+                    return "{...}".to_string();
+                }
+                printing.push(address);
+                let entries: Vec<String> = map.borrow()
+                    .entries()
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key.nested_to_string(printing, keep_fraction), 
+                        value.nested_to_string(printing, keep_fraction)))
+                    .collect();
+                printing.pop();
+                format!("{{{}}}", entries.join(", "))
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "Number({})", n),
+            Value::String(s) => write!(f, "String({})", s),
+            Value::Boolean(b) => write!(f, "Boolean({})", b),
+            Value::Nil => write!(f, "Nil"),
+            _ => write!(f, "{}", self),
+        }
+    }
+}
+
+// Follows `impl PartialEq for Value` in ./src/value.rs: functions, lists and
+// maps are equal only to themselves, instances are equal when their classes
+// and fields are.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Number(a), Value::Number(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Boolean(a), Value::Boolean(b)) => a == b,
+            (Value::Nil, Value::Nil) => true,
+            (Value::Function(a), Value::Function(b)) => Rc::ptr_eq(a, b),
+            (Value::Closure(a), Value::Closure(b)) => Rc::ptr_eq(a, b),
+            (Value::BoundMethod(a), Value::BoundMethod(b)) => Rc::ptr_eq(a, b),
+            (Value::Class(a), Value::Class(b)) => Rc::ptr_eq(a, b),
+            (Value::Instance(a), Value::Instance(b)) => {
+                Rc::ptr_eq(a, b) || {
+                    let (a, b) = (a.borrow(), b.borrow());
+                    Rc::ptr_eq(&a.class, &b.class) && a.fields == b.fields
+                }
+            }
+            (Value::Native(a), Value::Native(b)) => Rc::ptr_eq(a, b),
+            (Value::List(a), Value::List(b)) => Rc::ptr_eq(a, b),
+            (Value::Map(a), Value::Map(b)) => Rc::ptr_eq(a, b),
+            (Value::Module(a), Value::Module(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+// Map keys. Follows `impl Hash for Value` in ./src/value.rs.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Value::Number(n) => {
+                state.write_u8(0);
+                // 0.0 == -0.0, so they must hash the same.
+                let n = if *n == 0.0 { 0.0 } else { *n };
+                state.write_u64(n.to_bits());
+            }
+            Value::String(s) => {
+                state.write_u8(1);
+                s.hash(state);
+            }
+            Value::Boolean(b) => {
+                state.write_u8(2);
+                b.hash(state);
+            }
+            Value::Nil => state.write_u8(3),
+            _ => state.write_u8(4),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Number(n) => write!(f, "{}", n),
+            Value::String(s) => write!(f, "{}", s),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::Nil => write!(f, "nil"),
+            Value::Function(function) => write!(f, "<fn {}>", function.name),
+            Value::Closure(closure) => write!(f, "<fn {}>", closure.function.name),
+            Value::BoundMethod(bound) => write!(f, "<fn {}>", bound.method.function.name),
+            Value::Class(class) => write!(f, "{}", class.name),
+            Value::Instance(instance) => write!(f, "{} instance", instance.borrow().class.name),
+            Value::Native(native) => write!(f, "{}", native),
+            Value::List(_) | Value::Map(_) => write!(f, "{}", self.nested_to_string(&mut Vec::new(), false)),
+            Value::Module(module) => write!(f, "<module {}>", module.name),
+        }
+    }
+}
+
+/// Chapter 24: [Calls and Functions](https://craftinginterpreters.com/calls-and-functions.html).
+#[derive(Debug, Default)]
+pub struct ObjFunction {
+    // Empty for the top-level script.
+    pub name: String,
+    pub arity: usize,
+    pub upvalue_count: usize,
+    pub chunk: Chunk,
+    // Top-level statement offsets, for the top-level script only: after an
+    // uncaught runtime error, execution carries on with the next statement,
+    // the same as `Interpreter::interpret()`.
+    pub statements: Vec<usize>,
+}
+
+/// Chapter 25: [Closures](https://craftinginterpreters.com/closures.html).
+#[derive(Debug)]
+pub struct ObjClosure {
+    pub function: Rc<ObjFunction>,
+    pub upvalues: Vec<Rc<RefCell<ObjUpvalue>>>,
+    // Global variables of the module, or the script, which created the closure.
+    pub globals: Globals,
+    // The imported module which created the closure, `None` for the script.
+    pub source: Option<Rc<Path>>,
+}
+
+#[derive(Debug)]
+pub enum ObjUpvalue {
+    // The stack slot of a variable which is still on the stack.
+    Open(usize),
+    Closed(Value),
+}
+
+/// Chapter 27: [Classes and Instances](https://craftinginterpreters.com/classes-and-instances.html).
+#[derive(Debug)]
+pub struct ObjClass {
+    pub name: String,
+    pub methods: RefCell<Table<Rc<ObjClosure>>>,
+}
+
+impl ObjClass {
+    pub fn new(name: &str) -> Self {
+        ObjClass { name: name.to_string(), methods: RefCell::new(Table::default()) }
+    }
+
+    pub fn find_method(&self, name: &str) -> Option<Rc<ObjClosure>> {
+        self.methods.borrow().get(name).cloned()
+    }
+}
+
+#[derive(Debug)]
+pub struct ObjInstance {
+    pub class: Rc<ObjClass>,
+    pub fields: Table<Value>,
+}
+
+/// Chapter 28: [Methods and Initializers](https://craftinginterpreters.com/methods-and-initializers.html).
+#[derive(Debug)]
+pub struct ObjBoundMethod {
+    pub receiver: Value,
+    pub method: Rc<ObjClosure>,
+}
+
+/// See ./src/lox_module.rs.
+#[derive(Debug)]
+pub struct ObjModule {
+    pub name: String,
+    pub globals: Globals,
+}
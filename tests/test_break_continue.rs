@@ -0,0 +1,177 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/break/` and `./data/continue/`.
+//!
+//! `break` and `continue` are not in the book. Tests cover the parser, the
+//! [`rlox::resolver::Resolver`] checks, and the interpreter.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_break_continue
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_break_continue_parser_error -- --exact [--nocapture]
+//!     * cargo test test_break_continue_resolver_error -- --exact [--nocapture]
+//!     * cargo test test_break_continue_interpreter -- --exact [--nocapture]
+//!
+
+mod test_common;
+use rlox::parser::Parser;
+use rlox::resolver::Resolver;
+use crate::test_common::{
+    make_interpreter_byte_stream,
+    assert_scan_script,
+    assert_parse_script_statements,
+    TestScriptAndResult,
+    TestScriptAndResults,
+    assert_parser_result,
+    assert_resolver_result,
+    assert_interpreter_result,
+};
+
+fn get_parser_error_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/break/missing_semicolon.lox",
+            expected_result: false,
+            expected_output: vec!["[line 3] Error at '}': Expect ';' after 'break'."],
+        },
+    ]
+} // cargo test test_break_continue_parser_error -- --exact [--nocapture]
+
+fn get_resolver_error_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/break/at_top_level.lox",
+            expected_result: false,
+            expected_output: vec!["[line 1] Error at 'break': Can't use 'break' outside of a loop."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/break/in_function_in_loop.lox",
+            expected_result: false,
+            expected_output: vec!["[line 3] Error at 'break': Can't use 'break' outside of a loop."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/continue/at_top_level.lox",
+            expected_result: false,
+            expected_output: vec!["[line 1] Error at 'continue': Can't use 'continue' outside of a loop."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/continue/in_function_in_loop.lox",
+            expected_result: false,
+            expected_output: vec!["[line 3] Error at 'continue': Can't use 'continue' outside of a loop."],
+        },
+    ]
+} // cargo test test_break_continue_resolver_error -- --exact [--nocapture]
+
+fn get_interpreter_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/break/while.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["1.0", "2.0", "3.0", "done"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/break/for.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["0.0", "1.0", "done"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/break/nested.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["0.0", "10.0", "20.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/break/closure_in_body.lox",
+            expected_result: true,
+            expected_output: vec!["i"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/continue/while.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["1.0", "3.0", "5.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/continue/for.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["0.0", "2.0", "4.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/continue/nested.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["1.0", "3.0", "11.0", "13.0"],
+        },
+    ]
+} // cargo test test_break_continue_interpreter -- --exact [--nocapture]
+
+#[test]
+fn test_break_continue_parser_error() {
+    let parser_error_script_results = get_parser_error_script_results();
+
+    for entry in parser_error_script_results {
+        // Ensure script is loaded and scanned successfully.
+        let tokens = assert_scan_script(entry.script_name);
+
+        // Parsing test.
+        let mut parser = Parser::new(&tokens);
+        let res = parser.parse();
+
+        assert_parser_result(&entry, &res);
+    }
+}
+
+#[test]
+fn test_break_continue_resolver_error() {
+    let error_script_results = get_resolver_error_script_results();
+
+    // Resolver needs an mutable Interpreter instance.
+    let mut interpreter = make_interpreter_byte_stream();
+    // Resolver instance.
+    let mut resolver: Resolver = Resolver::new(&mut interpreter);
+
+    for entry in error_script_results {
+        // Ensure script is loaded, scanned and parsed successfully.
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        // Resolver test.
+        let res = resolver.resolve(&statements);
+
+        assert_resolver_result(&entry, &res);
+    }
+}
+
+#[test]
+fn test_break_continue_interpreter() {
+    let script_results = get_interpreter_script_results();
+
+    // Resolver needs an mutable Interpreter instance.
+    let mut interpreter = make_interpreter_byte_stream();
+
+    for entry in script_results {
+        interpreter.reset(true);
+
+        // Ensure script is loaded, scanned and parsed successfully.
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        // Create a resolver instance for each script file.
+        let mut resolver: Resolver = Resolver::new(&mut interpreter);
+
+        // Resolver test.
+        let res = resolver.resolve(&statements);
+
+        // Ensure resolving is successful.
+        assert!(res.is_ok(), "resolve error: {}", entry.script_name);
+
+        // Test interpreting/evaluating.
+        let res = interpreter.interpret(&statements);
+
+        assert_interpreter_result(&entry, &res, &interpreter);
+    }
+}
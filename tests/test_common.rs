@@ -12,6 +12,7 @@ use rlox::expr::Expr;
 use rlox::stmt::Stmt;
 use rlox::parser::Parser;
 use rlox::interpreter::{Writable, Interpreter};
+use rlox::vm::machine::Vm;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -228,26 +229,50 @@ pub fn make_interpreter_stdout() -> Interpreter {
 }
 
 #[allow(dead_code)]
+// Both the output and the error output are byte streams.
 pub fn make_interpreter_byte_stream() -> Interpreter {
-    make_interpreter(Cursor::new(Vec::new()))
+    make_interpreter(Cursor::new(Vec::new())).with_error_output(Cursor::new(Vec::new()))
 }
 
-#[allow(dead_code)]
-pub fn extract_output_lines(interpreter: &Interpreter) -> Vec<String> {
-    // Access the boxed output
-    let output_ref: &Box<dyn Writable> = interpreter.get_output();
-    
+// The lines written to a byte stream.
+fn byte_stream_lines(writer: &dyn Writable, name: &str) -> Vec<String> {
     // Downcast to the concrete type
-    if let Some(cursor) = output_ref.as_ref().as_any().downcast_ref::<Cursor<Vec<u8>>>() {
+    if let Some(cursor) = writer.as_any().downcast_ref::<Cursor<Vec<u8>>>() {
         let bytes = cursor.get_ref();
         String::from_utf8(bytes.clone()).unwrap()
             .lines()
             .map(|line| line.to_string())
             .collect()        
     } else {
-        panic!("Interpreter's output is not a Cursor<Vec<u8>>");
+        panic!("{} is not a Cursor<Vec<u8>>", name);
     }
+}
+
+#[allow(dead_code)]
+pub fn extract_output_lines(interpreter: &Interpreter) -> Vec<String> {
+    byte_stream_lines(interpreter.get_output().as_ref(), "Interpreter's output")
+}
+
+#[allow(dead_code)]
+// The runtime errors returned by `interpret()`, one line each, on both backends.
+pub fn extract_error_lines(result: &Result<(), LoxError>) -> Vec<String> {
+    match result {
+        Ok(_) => vec![],
+        Err(err) => err.errors().iter().map(|err| err.to_string()).collect(),
+    }
+}
+
+#[allow(dead_code)]
+// What `report_error()` wrote, see `make_interpreter_byte_stream()`.
+pub fn extract_reported_lines(interpreter: &Interpreter) -> Vec<String> {
+    byte_stream_lines(interpreter.get_error_output(), "Interpreter's error output")
+}
 
+#[allow(dead_code)]
+// `expected` has both the output and the errors in occurrence-order: splits it 
+// into the output and the errors, each still in order.
+pub fn split_expected_output<'a>(expected: &[&'a str], errors: &[String]) -> (Vec<&'a str>, Vec<&'a str>) {
+    expected.iter().partition(|line| !errors.iter().any(|err| err == *line))
 }
 
 #[allow(dead_code)]
@@ -259,29 +284,33 @@ pub fn extract_output_lines(interpreter: &Interpreter) -> Vec<String> {
 //    Derived.foo()
 //    [line 10] Error at ')': Expected 2 arguments but got 4.
 // 
-// Both are written to `Interpreter::output` in occurrence-order.
+// The output is written to `Interpreter::output`, the error is returned: 
+// `tested_entry.expected_output` lists both in occurrence-order.
 // While the returned error contains only error messages separated by 
 // a newline ( \n ) character.
 pub fn assert_interpreter_result(tested_entry: &TestScriptAndResult, 
     test_result: &InterpreterResult,
     interpreter: &Interpreter) {
 
-    // Interpreter::output contains all output in occurrence-order
-    // Extract output to test against expected output.
+    // Extract output and errors to test against expected output.
     let lines = extract_output_lines(&interpreter);
+    let error_lines = extract_error_lines(test_result);
+    let (expected_lines, expected_errors) = 
+        split_expected_output(&tested_entry.expected_output, &error_lines);
 
     match tested_entry.expected_result {
         true => {
             assert!(test_result.is_ok(), "1. Error in {}", tested_entry.script_name);
-            // Interpreter::output contains all output in occurrence-order
+            assert!(error_lines.is_empty(), "2. Error in {}", tested_entry.script_name);
             assert_eq!(lines, tested_entry.expected_output, 
                 "2. Error in {}", tested_entry.script_name);
         },
         false => {            
             assert!(test_result.is_err(), "3. Error in {}", tested_entry.script_name);
-            // Interpreter::output contains all output in occurrence-order
-            assert_eq!(lines, tested_entry.expected_output, 
+            assert_eq!(lines, expected_lines, 
                 "4.a. Error in {}", tested_entry.script_name);
+            assert_eq!(error_lines, expected_errors, 
+                "4.b. Error in {}", tested_entry.script_name);
 
             // The returned error contains only error messages separated by 
             // a newline ( \n ) character.                
@@ -298,3 +327,20 @@ pub fn assert_interpreter_result(tested_entry: &TestScriptAndResult,
         }
     }
 }
+
+#[allow(dead_code)]
+pub fn make_vm_byte_stream() -> Vm {
+    Vm::new(Cursor::new(Vec::new())).with_error_output(Cursor::new(Vec::new()))
+}
+
+#[allow(dead_code)]
+// The same as `extract_output_lines()`, for the bytecode VM.
+pub fn extract_vm_output_lines(vm: &Vm) -> Vec<String> {
+    byte_stream_lines(vm.get_output(), "Vm's output")
+}
+
+#[allow(dead_code)]
+// The same as `extract_reported_lines()`, for the bytecode VM.
+pub fn extract_vm_reported_lines(vm: &Vm) -> Vec<String> {
+    byte_stream_lines(vm.get_error_output(), "Vm's error output")
+}
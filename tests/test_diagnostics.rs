@@ -0,0 +1,229 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/diagnostics/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_diagnostics
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_diagnostics_parser -- --exact [--nocapture]
+//!     * cargo test test_diagnostics_scanner -- --exact [--nocapture]
+//!     * cargo test test_diagnostics_runtime -- --exact [--nocapture]
+//!     * cargo test test_diagnostics_module -- --exact [--nocapture]
+//!     * cargo test test_diagnostics_reported_once -- --exact [--nocapture]
+//!     * cargo test test_diagnostics_color -- --exact [--nocapture]
+//!
+
+mod test_common;
+
+use std::path::Path;
+
+use rlox::scanner::Scanner;
+use rlox::parser::Parser;
+use rlox::resolver::Resolver;
+use rlox::lox_error::LoxError;
+use rlox::token::Span;
+use rlox::diagnostics::{ColorChoice, Diagnostics};
+use crate::test_common::{
+    get_script_contents,
+    make_interpreter_byte_stream,
+    make_vm_byte_stream,
+    assert_parse_script_statements,
+    extract_reported_lines,
+    extract_vm_reported_lines,
+};
+
+// Runs the script on the tree-walker, or on the VM, and returns the first
+// phase which fails, and its error.
+fn run_script(script_name: &str, contents: &str, use_vm: bool) -> (&'static str, LoxError) {
+    let tokens = match Scanner::new(contents).scan_tokens() {
+        Ok(tokens) => tokens,
+        Err(err) => return ("Scanner", err),
+    };
+    let statements = match Parser::new(&tokens).parse() {
+        Ok(statements) => statements,
+        Err(err) => return ("Parser", err),
+    };
+
+    let mut interpreter = make_interpreter_byte_stream();
+    interpreter.set_script_path(script_name);
+    let mut resolver = Resolver::new(&mut interpreter);
+    if let Err(err) = resolver.resolve(&statements) {
+        return ("Resolver", err.into());
+    }
+
+    if use_vm {
+        let mut vm = make_vm_byte_stream();
+        vm.set_script_path(script_name);
+        ("VM", vm.interpret(&statements).expect_err("Expected a runtime error"))
+    } else {
+        ("Interpreter", interpreter.interpret(&statements).expect_err("Expected a runtime error"))
+    }
+}
+
+fn render(script_name: &str, use_vm: bool, color: bool) -> String {
+    let contents = get_script_contents(script_name).unwrap();
+    let (originator, err) = run_script(script_name, &contents, use_vm);
+
+    Diagnostics::new(&contents, script_name, color).render(originator, &err)
+}
+
+#[test]
+fn test_diagnostics_parser() {
+    let script_name = "./tests/data/diagnostics/invalid_assignment.lox";
+
+    assert_eq!(render(script_name, false, false), "\
+Parser error: Invalid assignment target.
+ --> ./tests/data/diagnostics/invalid_assignment.lox:3:7
+  |
+3 | a + b = 3;
+  |       ^
+  = help: only variables, properties and list or map elements can be assigned to.
+");
+}
+
+#[test]
+// All errors are rendered, each in its own block.
+fn test_diagnostics_scanner() {
+    let script_name = "./tests/data/diagnostics/scanner_errors.lox";
+
+    assert_eq!(render(script_name, false, false), "\
+Scanner error: Unexpected character: @.
+ --> ./tests/data/diagnostics/scanner_errors.lox:1:9
+  |
+1 | var a = @;
+  |         ^
+
+Scanner error: Unexpected character: #.
+ --> ./tests/data/diagnostics/scanner_errors.lox:2:9
+  |
+2 | print a # 1;
+  |         ^
+
+Scanner error: Unterminated string.
+ --> ./tests/data/diagnostics/scanner_errors.lox:3:7
+  |
+3 | print \"abc
+  |       ^^^^
+  = help: add a closing '\"' to end the string.
+");
+}
+
+#[test]
+// Both backends report runtime errors at the same tokens, with the same stack 
+// traces.
+fn test_diagnostics_runtime() {
+    let script_name = "./tests/data/diagnostics/runtime_errors.lox";
+
+    let expected = "\
+Interpreter error: Operand must be a number.
+ --> ./tests/data/diagnostics/runtime_errors.lox:1:11
+  |
+1 | print \"x\" - 1;
+  |           ^
+[line 1] in script
+
+Interpreter error: Undefined variable 'undefinedName'.
+ --> ./tests/data/diagnostics/runtime_errors.lox:2:7
+  |
+2 | print undefinedName;
+  |       ^^^^^^^^^^^^^
+[line 2] in script
+
+Interpreter error: Operand must be a number.
+ --> ./tests/data/diagnostics/runtime_errors.lox:3:9
+  |
+3 | \tprint  -\"tab\";
+  | \t       ^
+[line 3] in script
+";
+
+    assert_eq!(render(script_name, false, false), expected);
+    assert_eq!(render(script_name, true, false), expected.replace("Interpreter error", "VM error"));
+}
+
+#[test]
+// The error is in the module's file, not in the script's.
+fn test_diagnostics_module() {
+    let script_name = "./tests/data/diagnostics/module_error.lox";
+
+    let expected = "\
+Interpreter error: Operand must be a number.
+ --> tests/data/diagnostics/lib/faulty.lox:4:16
+  |
+4 |   return value - 1;
+  |                ^
+[line 4] in fail()
+[line 2] in script
+";
+
+    assert_eq!(render(script_name, false, false), expected);
+    assert_eq!(render(script_name, true, false), expected.replace("Interpreter error", "VM error"));
+
+    // The module's source can't be read: no source line.
+    let span = Span { line: 4, column: 16, end_line: 4, end_column: 17, start: 81, end: 82 };
+    let err = LoxError::new(4, "-", "Operand must be a number.")
+        .with_span(span)
+        .with_source_path(Path::new("./tests/data/diagnostics/lib/missing.lox"));
+    assert_eq!(Diagnostics::new("", script_name, false).render("Interpreter", &err), "\
+Interpreter error: Operand must be a number.
+ --> ./tests/data/diagnostics/lib/missing.lox:4:16
+");
+}
+
+#[test]
+// `interpret()` only returns the errors: the host's rendering is the only 
+// thing written to the error output.
+fn test_diagnostics_reported_once() {
+    let script_name = "./tests/data/diagnostics/runtime_errors.lox";
+    let contents = get_script_contents(script_name).unwrap();
+    let diagnostics = Diagnostics::new(&contents, script_name, false);
+    let statements = assert_parse_script_statements(script_name);
+
+    let mut interpreter = make_interpreter_byte_stream();
+    let mut resolver = Resolver::new(&mut interpreter);
+    assert!(resolver.resolve(&statements).is_ok());
+
+    let err = interpreter.interpret(&statements).expect_err("Expected a runtime error");
+    assert!(extract_reported_lines(&interpreter).is_empty());
+    let report = diagnostics.render("Interpreter", &err);
+    interpreter.report_error(&report);
+    assert_eq!(extract_reported_lines(&interpreter).join("\n") + "\n", report);
+
+    let mut vm = make_vm_byte_stream();
+    let err = vm.interpret(&statements).expect_err("Expected a runtime error");
+    assert!(extract_vm_reported_lines(&vm).is_empty());
+    let report = diagnostics.render("VM", &err);
+    vm.report_error(&report);
+    assert_eq!(extract_vm_reported_lines(&vm).join("\n") + "\n", report);
+}
+
+#[test]
+fn test_diagnostics_color() {
+    assert_eq!(ColorChoice::parse("always"), Some(ColorChoice::Always));
+    assert_eq!(ColorChoice::parse("never"), Some(ColorChoice::Never));
+    assert_eq!(ColorChoice::parse("auto"), Some(ColorChoice::Auto));
+    assert_eq!(ColorChoice::parse("sometimes"), None);
+
+    assert!(ColorChoice::Always.enabled());
+    assert!(!ColorChoice::Never.enabled());
+
+    let script_name = "./tests/data/diagnostics/invalid_assignment.lox";
+
+    let plain = render(script_name, false, false);
+    assert!(!plain.contains('\x1b'));
+
+    let coloured = render(script_name, false, true);
+    assert!(coloured.contains("\x1b[1;31mParser error\x1b[0m"));
+    assert!(coloured.contains("\x1b[1;31m^\x1b[0m"));
+
+    // Without the colour codes, the text is the same.
+    let stripped = coloured
+        .replace("\x1b[1;31m", "")
+        .replace("\x1b[1;34m", "")
+        .replace("\x1b[1m", "")
+        .replace("\x1b[0m", "");
+    assert_eq!(stripped, plain);
+}
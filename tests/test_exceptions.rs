@@ -0,0 +1,181 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/exception/`.
+//!
+//! `throw` and `try`/`catch`/`finally` are not in the book. Tests cover the 
+//! parser, the interpreter and the VM.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_exceptions
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_exceptions_parser_error -- --exact [--nocapture]
+//!     * cargo test test_exceptions_interpreter -- --exact [--nocapture]
+//!     * cargo test test_exceptions_uncaught -- --exact [--nocapture]
+//!
+
+mod test_common;
+use rlox::parser::Parser;
+use rlox::resolver::Resolver;
+use rlox::diagnostics::Diagnostics;
+use crate::test_common::{
+    get_script_contents,
+    make_interpreter_byte_stream,
+    make_vm_byte_stream,
+    extract_output_lines,
+    extract_vm_output_lines,
+    assert_scan_script,
+    assert_parse_script_statements,
+    TestScriptAndResult,
+    TestScriptAndResults,
+    assert_parser_result,
+    assert_interpreter_result,
+};
+
+fn get_parser_error_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/missing_catch.lox",
+            expected_result: false,
+            expected_output: vec!["[line 4] Error at 'print': Expect 'catch' or 'finally' after try block."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/missing_semicolon.lox",
+            expected_result: false,
+            // At the end of the source, on the line after its last newline.
+            expected_output: vec!["[line 2] Error at '': Expect ';' after thrown value."],
+        },
+    ]
+} // cargo test test_exceptions_parser_error -- --exact [--nocapture]
+
+fn get_interpreter_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/throw_catch.lox",
+            expected_result: true,
+            expected_output: vec!["before", "oops", "after"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/native_error.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["Error instance", "Operand must be a number.", "2.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/finally.lox",
+            expected_result: true,
+            expected_output: vec!["try", "finally", "catch", "finally"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/finally_on_return.lox",
+            expected_result: true,
+            expected_output: vec!["finally", "returned"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/finally_on_break.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["0.0", "finally", "finally"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/throw_from_function.lox",
+            expected_result: true,
+            expected_output: vec!["negative"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/rethrow.lox",
+            expected_result: true,
+            expected_output: vec!["inner finally", "outer caught inner"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/uncaught.lox",
+            expected_result: false,
+            expected_output: vec!["start", "[line 2] Error at 'throw': Uncaught exception: oops.", "end"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/exception/uncaught_native.lox",
+            expected_result: false,
+            expected_output: vec!["[line 2] Error: Operands must be two numbers or two strings."],
+        },
+    ]
+} // cargo test test_exceptions_interpreter -- --exact [--nocapture]
+
+#[test]
+fn test_exceptions_parser_error() {
+    let parser_error_script_results = get_parser_error_script_results();
+
+    for entry in parser_error_script_results {
+        // Ensure script is loaded and scanned successfully.
+        let tokens = assert_scan_script(entry.script_name);
+
+        // Parsing test.
+        let mut parser = Parser::new(&tokens);
+        let res = parser.parse();
+
+        assert_parser_result(&entry, &res);
+    }
+}
+
+#[test]
+fn test_exceptions_interpreter() {
+    let script_results = get_interpreter_script_results();
+
+    // Resolver needs an mutable Interpreter instance.
+    let mut interpreter = make_interpreter_byte_stream();
+
+    for entry in script_results {
+        interpreter.reset(true);
+
+        // Ensure script is loaded, scanned and parsed successfully.
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        // Create a resolver instance for each script file.
+        let mut resolver: Resolver = Resolver::new(&mut interpreter);
+
+        // Resolver test.
+        let res = resolver.resolve(&statements);
+
+        // Ensure resolving is successful.
+        assert!(res.is_ok(), "resolve error: {}", entry.script_name);
+
+        // Test interpreting/evaluating.
+        let res = interpreter.interpret(&statements);
+
+        assert_interpreter_result(&entry, &res, &interpreter);
+    }
+}
+
+#[test]
+// Reported at the `throw` keyword, with the stack trace where the value was 
+// thrown, even after a `finally` block has run.
+fn test_exceptions_uncaught() {
+    let script_name = "./tests/data/exception/uncaught_in_function.lox";
+    let statements = assert_parse_script_statements(script_name);
+
+    let mut interpreter = make_interpreter_byte_stream();
+    let mut resolver = Resolver::new(&mut interpreter);
+    assert!(resolver.resolve(&statements).is_ok());
+    let interpreter_err = interpreter.interpret(&statements).expect_err("Expected an uncaught exception");
+    assert_eq!(extract_output_lines(&interpreter), vec!["finally"]);
+
+    let mut vm = make_vm_byte_stream();
+    let vm_err = vm.interpret(&statements).expect_err("Expected an uncaught exception");
+    assert_eq!(extract_vm_output_lines(&vm), vec!["finally"]);
+
+    let contents = get_script_contents(script_name).unwrap();
+    let diagnostics = Diagnostics::new(&contents, script_name, false);
+    for err in [&interpreter_err, &vm_err] {
+        assert_eq!(err.to_string(), "[line 2] Error at 'throw': Uncaught exception: negative.");
+        assert_eq!(diagnostics.render("Interpreter", err), "\
+Interpreter error: Uncaught exception: negative.
+ --> ./tests/data/exception/uncaught_in_function.lox:2:14
+  |
+2 |   if (n < 0) throw \"negative\";
+  |              ^^^^^
+[line 2] in check()
+[line 7] in script
+");
+    }
+}
@@ -0,0 +1,80 @@
+// Date Created: 18/10/2026.
+
+//! Uses data from `./data/exit_code/`.
+//!
+//! Runs the `rlox` binary, as a shell would.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_exit_codes
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_exit_codes -- --exact [--nocapture]
+//!     * cargo test test_exit_codes_vm -- --exact [--nocapture]
+//!     * cargo test test_exit_codes_io -- --exact [--nocapture]
+//!     * cargo test test_exit_codes_scan_and_parse -- --exact [--nocapture]
+//!
+
+use std::process::{Command, Output};
+
+fn run_rlox(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rlox"))
+        .args(args)
+        .output()
+        .expect("Failed to run rlox")
+}
+
+fn assert_exit_codes(options: &[&str]) {
+    for (script_name, exit_code) in [
+        ("./tests/data/exit_code/ok.lox", 0),
+        ("./tests/data/exit_code/scan_error.lox", 65),
+        ("./tests/data/exit_code/parse_error.lox", 65),
+        ("./tests/data/exit_code/resolve_error.lox", 65),
+        ("./tests/data/exit_code/runtime_error.lox", 70),
+    ] {
+        let mut args = options.to_vec();
+        args.push(script_name);
+        let output = run_rlox(&args);
+        assert_eq!(output.status.code(), Some(exit_code), "Error in {}", script_name);
+    }
+}
+
+#[test]
+fn test_exit_codes() {
+    assert_exit_codes(&[]);
+
+    // The output and the errors are kept apart.
+    let output = run_rlox(&["./tests/data/exit_code/runtime_error.lox"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "before\nafter\n");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Operand must be a number."));
+}
+
+#[test]
+fn test_exit_codes_vm() {
+    assert_exit_codes(&["--vm"]);
+}
+
+#[test]
+fn test_exit_codes_io() {
+    let output = run_rlox(&["./tests/data/exit_code/missing.lox"]);
+    assert_eq!(output.status.code(), Some(74));
+
+    // Not a file.
+    let output = run_rlox(&["./tests/data/exit_code"]);
+    assert_eq!(output.status.code(), Some(74));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Can't read './tests/data/exit_code'"));
+}
+
+#[test]
+fn test_exit_codes_scan_and_parse() {
+    // Parser errors are reported with scanner errors, in the same run.
+    let output = run_rlox(&["--color=never", "./tests/data/scanning/recovery.lox"]);
+    assert_eq!(output.status.code(), Some(65));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stderr.matches("Scanner error:").count(), 3);
+    assert!(stderr.contains("Parser error: Expect ';' after variable declaration."));
+    assert!(stderr.contains("Parser error: Expect expression."));
+    assert!(output.stdout.is_empty());
+}
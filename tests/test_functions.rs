@@ -67,7 +67,7 @@ fn get_function_objects_script_results<'a>() -> TestScriptAndResults<'a> {
         TestScriptAndResult {
             script_name: "./tests/data/function/print.lox",
             expected_result: true,
-            expected_output: vec!["<fn foo>", "<native fn>"],
+            expected_output: vec!["<fn foo>", "<native fn clock>"],
         },                
         // Author's https://github.com/munificent/craftinginterpreters/tree/master/test/operator
         TestScriptAndResult {
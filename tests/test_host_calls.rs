@@ -0,0 +1,93 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/host_call/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_host_calls
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_host_calls_functions -- --exact [--nocapture]
+//!     * cargo test test_host_calls_methods -- --exact [--nocapture]
+//!     * cargo test test_host_calls_errors -- --exact [--nocapture]
+//!
+
+use std::io::Cursor;
+
+use rlox::Lox;
+use rlox::lox_error::Phase;
+use rlox::value::Value;
+
+fn load_script() -> Lox {
+    let mut lox = Lox::with_output(Cursor::new(Vec::new()));
+    lox.run_file("./tests/data/host_call/callbacks.lox").unwrap();
+    lox
+}
+
+fn global(lox: &Lox, name: &str) -> Value {
+    lox.get_global(name).unwrap_or_else(|| panic!("Undefined global {}", name))
+}
+
+#[test]
+fn test_host_calls_functions() {
+    let mut lox = load_script();
+
+    let on_click = global(&lox, "onClick");
+    let value = lox.call_value(&on_click, vec![Value::String("ok".to_string())]).unwrap();
+    assert_eq!(value, Value::String("clicked ok".to_string()));
+    assert_eq!(global(&lox, "handled"), Value::Number(1.0));
+
+    // A comparator: sorts in descending order.
+    let compare = global(&lox, "compare");
+    let mut numbers = vec![2.0, 3.0, 1.0];
+    numbers.sort_by(|a, b| {
+        match lox.call_value(&compare, vec![Value::Number(*a), Value::Number(*b)]).unwrap() {
+            Value::Number(n) => n.partial_cmp(&0.0).unwrap(),
+            value => panic!("Expected a number, got {}", value),
+        }
+    });
+    assert_eq!(numbers, vec![3.0, 2.0, 1.0]);
+}
+
+#[test]
+fn test_host_calls_methods() {
+    let mut lox = load_script();
+
+    let counter = global(&lox, "counter");
+    assert_eq!(lox.call_method(&counter, "add", vec![Value::Number(5.0)]).unwrap(), Value::Number(15.0));
+    assert_eq!(lox.eval("counter.count;").unwrap(), Value::Number(15.0));
+
+    // Calling a class creates an instance.
+    let class = global(&lox, "Counter");
+    let other = lox.call_value(&class, vec![Value::Number(1.0)]).unwrap();
+    assert_eq!(lox.call_method(&other, "add", vec![Value::Number(1.0)]).unwrap(), Value::Number(2.0));
+
+    let list = lox.eval("[1, 2];").unwrap();
+    lox.call_method(&list, "push", vec![Value::Number(3.0)]).unwrap();
+    assert_eq!(lox.call_method(&list, "len", vec![]).unwrap(), Value::Number(3.0));
+}
+
+#[test]
+fn test_host_calls_errors() {
+    let mut lox = load_script();
+
+    let on_click = global(&lox, "onClick");
+    let counter = global(&lox, "counter");
+    let broken = global(&lox, "broken");
+
+    let err = lox.call_value(&on_click, vec![]).expect_err("Expected an error");
+    assert_eq!(err.to_string(), "Expected 1 arguments but got 0.");
+    assert_eq!(err.phase(), Some(Phase::Runtime));
+
+    let err = lox.call_value(&Value::Number(1.0), vec![]).expect_err("Expected an error");
+    assert_eq!(err.to_string(), "Can only call functions and classes.");
+
+    let err = lox.call_method(&counter, "missing", vec![]).expect_err("Expected an error");
+    assert_eq!(err.to_string(), "Error at 'missing': Undefined property 'missing'.");
+
+    // Errors in Lox code are at its tokens.
+    let err = lox.call_value(&broken, vec![]).expect_err("Expected an error");
+    assert_eq!(err.to_string(), "[line 26] Error at '+': Operands must be two numbers or two strings.");
+    assert_eq!(err.trace()[0], "[line 26] in broken()");
+}
@@ -0,0 +1,135 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/host_object/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_host_objects
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_host_objects -- --exact [--nocapture]
+//!     * cargo test test_host_objects_errors -- --exact [--nocapture]
+//!
+
+mod test_common;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use rlox::interpreter::Interpreter;
+use rlox::lox_error_helper::sys_error;
+use rlox::lox_host::HostObject;
+use rlox::lox_native::Arity;
+use rlox::lox_runtime_error::LoxRuntimeError;
+use rlox::resolver::Resolver;
+use rlox::value::Value;
+use crate::test_common::{
+    assert_parse_script_statements,
+    make_interpreter_byte_stream,
+    extract_output_lines,
+    extract_error_lines,
+};
+
+// `name` is read-only, `retries` a number.
+struct Config {
+    retries: Cell<f64>,
+    logged: RefCell<Vec<String>>,
+}
+
+impl HostObject for Config {
+    fn name(&self) -> &str {
+        "Config"
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        match name {
+            "name" => Some(Value::from("test")),
+            "retries" => Some(Value::from(self.retries.get())),
+            _ => None,
+        }
+    }
+
+    fn set(&self, name: &str, value: Value) -> Result<(), LoxRuntimeError> {
+        match name {
+            "retries" => self.retries.set(f64::try_from(value)?),
+            _ => return Err(sys_error("", &format!("Can't set property '{}' of Config.", name)).into()),
+        }
+        Ok(())
+    }
+
+    fn method_arity(&self, name: &str) -> Option<Arity> {
+        match name {
+            "lookup" => Some(Arity::Fixed(1)),
+            "log" => Some(Arity::Variadic),
+            _ => None,
+        }
+    }
+
+    fn call_method(&self, _interpreter: &mut Interpreter, name: &str,
+        arguments: &[Value]) -> Result<Value, LoxRuntimeError> {
+        match name {
+            "lookup" => match String::try_from(arguments[0].clone())?.as_str() {
+                "host" => Ok(Value::from("localhost")),
+                key => Err(sys_error("", &format!("Undefined key '{}'.", key)).into()),
+            },
+            _ => {
+                let line: Vec<String> = arguments.iter().map(|arg| arg.to_string()).collect();
+                self.logged.borrow_mut().push(line.join(" "));
+                Ok(Value::Nil)
+            }
+        }
+    }
+}
+
+// Runs the script with `config` defined, returns its output and its error 
+// output.
+fn run_script(script_name: &str, config: &Rc<Config>) -> (Vec<String>, Vec<String>) {
+    let statements = assert_parse_script_statements(script_name);
+
+    let mut interpreter = make_interpreter_byte_stream();
+    interpreter.define_global("config", Value::Host(Rc::clone(config) as Rc<dyn HostObject>));
+    let mut resolver = Resolver::new(&mut interpreter);
+    assert!(resolver.resolve(&statements).is_ok(), "Error in {}", script_name);
+
+    let result = interpreter.interpret(&statements);
+    (extract_output_lines(&interpreter), extract_error_lines(&result))
+}
+
+fn make_config() -> Rc<Config> {
+    Rc::new(Config { retries: Cell::new(2.0), logged: RefCell::new(vec![]) })
+}
+
+#[test]
+fn test_host_objects() {
+    let config = make_config();
+
+    let (output, errors) = run_script("./tests/data/host_object/config.lox", &config);
+    assert!(errors.is_empty());
+    assert_eq!(output, vec![
+        "Config instance",
+        "test",
+        "3.0",
+        "localhost",
+        "<native fn lookup>",
+        "true",
+    ]);
+    assert_eq!(config.retries.get(), 3.0);
+    assert_eq!(*config.logged.borrow(), vec!["retries 3 true"]);
+}
+
+#[test]
+fn test_host_objects_errors() {
+    let config = make_config();
+
+    let (output, errors) = run_script("./tests/data/host_object/errors.lox", &config);
+    assert!(output.is_empty());
+    assert_eq!(errors, vec![
+        "[line 1] Error at 'missing': Undefined property 'missing'.",
+        "[line 2] Error at 'name': Can't set property 'name' of Config.",
+        "[line 3] Error at 'retries': Expected a number but got a string.",
+        "[line 4] Error at ')': Expected 1 arguments but got 0.",
+        "[line 5] Error at ')': Undefined key 'port'.",
+    ]);
+    assert_eq!(config.retries.get(), 2.0);
+}
@@ -0,0 +1,181 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/limits/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_limits
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_limits_call_depth_interpreter -- --exact [--nocapture]
+//!     * cargo test test_limits_call_depth_vm -- --exact [--nocapture]
+//!     * cargo test test_limits_call_depth_default -- --exact [--nocapture]
+//!     * cargo test test_limits_nesting -- --exact [--nocapture]
+//!     * cargo test test_limits_chains -- --exact [--nocapture]
+//!     * cargo test test_limits_default_stack -- --exact [--nocapture]
+//!
+
+mod test_common;
+
+use std::io::sink;
+use std::thread;
+
+use rlox::Lox;
+use rlox::interpreter::DEFAULT_MAX_CALL_DEPTH;
+use rlox::lox_error::Phase;
+use rlox::parser::{Parser, DEFAULT_MAX_NESTING};
+use rlox::scanner::Scanner;
+use rlox::resolver::Resolver;
+use crate::test_common::{
+    assert_parse_script_statements,
+    assert_scan_script,
+    make_interpreter_byte_stream,
+    make_vm_byte_stream,
+    extract_output_lines,
+    extract_error_lines,
+    extract_vm_output_lines,
+};
+
+const STACK_OVERFLOW: &str = "[line 3] Error at ')': Stack overflow.";
+
+// Counting the script, `max_depth` calls are in progress at the failing call.
+fn expected_depths(max_depth: usize) -> Vec<String> {
+    (1..max_depth).map(|depth| format!("{}.0", depth)).collect()
+}
+
+#[test]
+fn test_limits_call_depth_interpreter() {
+    let statements = assert_parse_script_statements("./tests/data/limits/recursion.lox");
+
+    let mut interpreter = make_interpreter_byte_stream();
+    interpreter.set_max_call_depth(64);
+    let mut resolver = Resolver::new(&mut interpreter);
+    assert!(resolver.resolve(&statements).is_ok());
+
+    let result = interpreter.interpret(&statements);
+    let err = result.as_ref().expect_err("Expected a runtime error");
+    assert_eq!(err.errors()[0].err_msg(), "Stack overflow.");
+    assert_eq!(err.errors()[0].line(), 3);
+    assert_eq!(extract_output_lines(&interpreter), expected_depths(64));
+    assert_eq!(extract_error_lines(&result), vec![STACK_OVERFLOW]);
+}
+
+#[test]
+fn test_limits_call_depth_vm() {
+    let statements = assert_parse_script_statements("./tests/data/limits/recursion.lox");
+
+    let mut interpreter = make_interpreter_byte_stream();
+    let mut resolver = Resolver::new(&mut interpreter);
+    assert!(resolver.resolve(&statements).is_ok());
+
+    let mut vm = make_vm_byte_stream();
+    vm.set_max_call_depth(64);
+    let result = vm.interpret(&statements);
+    let err = result.as_ref().expect_err("Expected a runtime error");
+    assert_eq!(err.errors()[0].err_msg(), "Stack overflow.");
+    assert_eq!(err.errors()[0].line(), 3);
+    assert_eq!(extract_vm_output_lines(&vm), expected_depths(64));
+    assert_eq!(extract_error_lines(&result), vec![STACK_OVERFLOW]);
+}
+
+#[test]
+// On the test thread's standard stack: the error, not a Rust stack overflow.
+fn test_limits_call_depth_default() {
+    let statements = assert_parse_script_statements("./tests/data/limits/recursion.lox");
+
+    let mut interpreter = make_interpreter_byte_stream();
+    let mut resolver = Resolver::new(&mut interpreter);
+    assert!(resolver.resolve(&statements).is_ok());
+
+    let err = interpreter.interpret(&statements).expect_err("Expected a runtime error");
+    assert_eq!(err.errors()[0].err_msg(), "Stack overflow.");
+    assert_eq!(extract_output_lines(&interpreter), expected_depths(DEFAULT_MAX_CALL_DEPTH));
+
+    // Each call also nests expressions.
+    let mut lox = Lox::with_output(sink());
+    let err = lox.eval("fun f(n) { return f(n + 1) + 1; } f(0);").expect_err("Expected a runtime error");
+    assert_eq!(err.err_msg(), "Stack overflow.");
+}
+
+#[test]
+fn test_limits_nesting() {
+    for (script_name, line) in [
+        ("./tests/data/limits/nested_groups.lox", 1),
+        ("./tests/data/limits/nested_blocks.lox", 34),
+    ] {
+        let tokens = assert_scan_script(script_name);
+
+        // The default limit is well above 40 levels.
+        assert!(Parser::new(&tokens).parse().is_ok(), "Error in {}", script_name);
+
+        let mut parser = Parser::new(&tokens);
+        parser.set_max_nesting(32);
+        let err = parser.parse().expect_err("Expected a parser error");
+        let errors = err.errors();
+        assert_eq!(errors[0].err_msg(), "Too much nesting.", "Error in {}", script_name);
+        assert_eq!(errors[0].line(), line, "Error in {}", script_name);
+    }
+}
+
+#[test]
+// Left-deep chains of operators, calls and property accesses count against 
+// the nesting limit: a long one is a parser error, not a Rust stack overflow.
+fn test_limits_chains() {
+    let mut lox = Lox::with_output(sink());
+    for chain in [
+        format!("print 1{};", " + 1".repeat(20_000)),
+        format!("print true{};", " and true".repeat(20_000)),
+        format!("print nil{};", ".next".repeat(20_000)),
+    ] {
+        let err = lox.eval(&chain).expect_err("Expected a parser error");
+        assert_eq!(err.phase(), Some(Phase::Parse));
+        assert_eq!(err.errors()[0].err_msg(), "Too much nesting.");
+    }
+
+    // The statement's expression is one level, each operator one more.
+    for (operators, ok) in [(31, true), (32, false)] {
+        let tokens = Scanner::new(&format!("print 1{};", " + 1".repeat(operators)))
+            .scan_tokens().unwrap();
+        let mut parser = Parser::new(&tokens);
+        parser.set_max_nesting(32);
+        let result = parser.parse();
+        assert_eq!(result.is_ok(), ok, "{} operators", operators);
+        if let Err(err) = result {
+            // At the 32nd operator.
+            assert_eq!(err.errors()[0].lexeme(), "+");
+            assert_eq!(err.errors()[0].span().column, 9 + 4 * 31);
+        }
+    }
+}
+
+#[test]
+// At the default limits, the deepest nesting and recursion the limits allow 
+// run on a standard 2MB thread, and one more level is a Lox error.
+fn test_limits_default_stack() {
+    let runner = thread::Builder::new().stack_size(2 * 1024 * 1024).spawn(|| {
+        let mut lox = Lox::with_output(sink());
+        // The statement's expression is one level, each group one more.
+        let groups = |levels: usize| format!("print {}1{};", "(".repeat(levels), ")".repeat(levels));
+        assert!(lox.eval(&groups(DEFAULT_MAX_NESTING - 1)).is_ok());
+        let err = lox.eval(&groups(DEFAULT_MAX_NESTING)).expect_err("Expected a parser error");
+        assert_eq!(err.errors()[0].err_msg(), "Too much nesting.");
+
+        let chain = |operators: usize| format!("print 1{};", " + 1".repeat(operators));
+        assert!(lox.eval(&chain(DEFAULT_MAX_NESTING - 1)).is_ok());
+        let err = lox.eval(&chain(DEFAULT_MAX_NESTING)).expect_err("Expected a parser error");
+        assert_eq!(err.errors()[0].err_msg(), "Too much nesting.");
+
+        // Each call nests expressions as deeply as the parser allows: the 
+        // stack runs out before the call depth does.
+        let recursion = format!("fun f(n) {{ return {}f(n + 1){}; }} f(0);",
+            "(".repeat(DEFAULT_MAX_NESTING - 5), ")".repeat(DEFAULT_MAX_NESTING - 5));
+        let err = lox.eval(&recursion).expect_err("Expected a runtime error");
+        assert_eq!(err.err_msg(), "Stack overflow.");
+
+        let source = std::fs::read_to_string("./tests/data/limits/recursion.lox").unwrap();
+        let err = lox.eval(&source).expect_err("Expected a runtime error");
+        assert_eq!(err.err_msg(), "Stack overflow.");
+    }).unwrap();
+    assert!(runner.join().is_ok());
+}
@@ -0,0 +1,188 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/list/`.
+//!
+//! Lists are not in the book. Tests cover list literals, subscript get and set,
+//! and the native list methods `push`, `pop`, `len`, `insert` and `remove`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_lists
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_lists_parser_error -- --exact [--nocapture]
+//!     * cargo test test_lists_interpreter -- --exact [--nocapture]
+//!     * cargo test test_lists_cyclic_display -- --exact [--nocapture]
+//!     * cargo test test_lists_cyclic_debug -- --exact [--nocapture]
+//!
+
+mod test_common;
+use std::io::sink;
+
+use rlox::Lox;
+use rlox::parser::Parser;
+use rlox::resolver::Resolver;
+use crate::test_common::{
+    make_interpreter_byte_stream,
+    assert_scan_script,
+    assert_parse_script_statements,
+    TestScriptAndResult,
+    TestScriptAndResults,
+    assert_parser_result,
+    assert_interpreter_result,
+};
+
+fn get_parser_error_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/list/missing_bracket.lox",
+            expected_result: false,
+            expected_output: vec![
+                "[line 1] Error at ';': Expect ']' after list elements.",
+                "[line 3] Error at ';': Expect ']' after index.",
+            ],
+        },
+    ]
+} // cargo test test_lists_parser_error -- --exact [--nocapture]
+
+fn get_interpreter_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/list/literal.lox",
+            expected_result: true,
+            expected_output: vec!["[]", "[1.0, two, true, nil]", "[3.0, [3.0]]"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/index.lox",
+            expected_result: true,
+            expected_output: vec!["10.0", "30.0", "20.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/index_set.lox",
+            expected_result: true,
+            expected_output: vec!["b", "[0.0, b, 0.0]"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/methods.lox",
+            expected_result: true,
+            expected_output: vec!["3.0", "3.0", "[first, 1.0, 2.0, last]", 
+                "1.0", "[first, 2.0, last]", "4.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/shared.lox",
+            expected_result: true,
+            expected_output: vec!["[1.0, 2.0]", "true", "false", "[1.0, 2.0, 3.0]"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/nested.lox",
+            expected_result: true,
+            expected_output: vec!["30.0", "[[1.0, 2.0], [30.0, 4.0]]", "x"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/cyclic.lox",
+            expected_result: true,
+            expected_output: vec!["[1.0, 2.0, [...]]", "1.0", "[[3.0], [3.0]]"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/index_out_of_range.lox",
+            expected_result: false,
+            expected_output: vec!["2.0", "[line 3] Error at ']': List index out of range."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/index_not_integer.lox",
+            expected_result: false,
+            expected_output: vec![
+                "[line 2] Error at ']': List index must be an integer.",
+                "[line 3] Error at ']': List index must be a number.",
+            ],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/index_on_non_list.lox",
+            expected_result: false,
+            expected_output: vec!["[line 2] Error at ']': Only lists and maps can be indexed."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/pop_empty.lox",
+            expected_result: false,
+            expected_output: vec![
+                "[line 2] Error at 'pop': Can't pop from an empty list.",
+                "[line 3] Error at 'remove': List index out of range.",
+            ],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/list/undefined_method.lox",
+            expected_result: false,
+            expected_output: vec![
+                "[line 2] Error at 'size': Undefined property 'size'.",
+                "[line 3] Error at ')': Expected 1 arguments but got 2.",
+            ],
+        },
+    ]
+} // cargo test test_lists_interpreter -- --exact [--nocapture]
+
+#[test]
+fn test_lists_parser_error() {
+    let parser_error_script_results = get_parser_error_script_results();
+
+    for entry in parser_error_script_results {
+        // Ensure script is loaded and scanned successfully.
+        let tokens = assert_scan_script(entry.script_name);
+
+        // Parsing test.
+        let mut parser = Parser::new(&tokens);
+        let res = parser.parse();
+
+        assert_parser_result(&entry, &res);
+    }
+}
+
+#[test]
+fn test_lists_interpreter() {
+    let script_results = get_interpreter_script_results();
+
+    // Resolver needs an mutable Interpreter instance.
+    let mut interpreter = make_interpreter_byte_stream();
+
+    for entry in script_results {
+        interpreter.reset(true);
+
+        // Ensure script is loaded, scanned and parsed successfully.
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        // Create a resolver instance for each script file.
+        let mut resolver: Resolver = Resolver::new(&mut interpreter);
+
+        // Resolver test.
+        let res = resolver.resolve(&statements);
+
+        // Ensure resolving is successful.
+        assert!(res.is_ok(), "resolve error: {}", entry.script_name);
+
+        // Test interpreting/evaluating.
+        let res = interpreter.interpret(&statements);
+
+        assert_interpreter_result(&entry, &res, &interpreter);
+    }
+}
+
+#[test]
+// Rust's `Display`, as hosts see values: a list inside itself is `[...]`.
+fn test_lists_cyclic_display() {
+    let mut lox = Lox::with_output(sink());
+
+    let list = lox.eval("var a = [1, {}]; a.push(a); a[1][\"a\"] = a; a;").unwrap();
+    assert_eq!(list.to_string(), "[1, {a: [...]}, [...]]");
+}
+
+#[test]
+// `Debug` goes through the same guard as `Display`.
+fn test_lists_cyclic_debug() {
+    let mut lox = Lox::with_output(sink());
+
+    let list = lox.eval("var a = [1, {}]; a.push(a); a[1][\"a\"] = a; a;").unwrap();
+    assert_eq!(format!("{:?}", list), "List([1, {a: [...]}, [...]])");
+
+    let map = lox.eval("a[1];").unwrap();
+    assert_eq!(format!("{:?}", map), "Map({a: [1, {...}, [...]]})");
+}
@@ -0,0 +1,83 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/embedding/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_lox
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_lox_eval -- --exact [--nocapture]
+//!     * cargo test test_lox_errors -- --exact [--nocapture]
+//!     * cargo test test_lox_run_file -- --exact [--nocapture]
+//!
+
+mod test_common;
+
+use std::io::Cursor;
+
+use rlox::Lox;
+use rlox::lox_error::Phase;
+use rlox::value::Value;
+use crate::test_common::extract_output_lines;
+
+#[test]
+// State persists between calls.
+fn test_lox_eval() {
+    let mut lox = Lox::with_output(Cursor::new(Vec::new()));
+
+    lox.set_global("base", Value::Number(40.0));
+    assert_eq!(lox.eval("var answer = base + 2;").unwrap(), Value::Nil);
+    assert_eq!(lox.get_global("answer"), Some(Value::Number(42.0)));
+    assert_eq!(lox.get_global("missing"), None);
+
+    lox.eval("fun makeCounter() { var i = 0; fun count() { i = i + 1; return i; } return count; }").unwrap();
+    lox.eval("var counter = makeCounter();").unwrap();
+    lox.eval("counter();").unwrap();
+    assert_eq!(lox.eval("counter();").unwrap(), Value::Number(2.0));
+
+    assert_eq!(lox.eval("print answer; \"last\";").unwrap(), Value::String("last".to_string()));
+    assert_eq!(extract_output_lines(lox.interpreter()), vec!["42.0"]);
+}
+
+#[test]
+fn test_lox_errors() {
+    let mut lox = Lox::with_output(Cursor::new(Vec::new()));
+
+    for (source, phase, err_msg) in [
+        ("var a = @;", Phase::Scan, "Unexpected character: @."),
+        ("var = 1;", Phase::Parse, "Expect variable name."),
+        ("return 1;", Phase::Resolve, "Can't return from top-level code."),
+        ("var a = 1; a();", Phase::Runtime, "Can only call functions and classes."),
+    ] {
+        let err = lox.eval(source).expect_err("Expected an error");
+        assert_eq!(err.phase(), Some(phase), "Error in {}", source);
+        assert_eq!(err.errors()[0].err_msg(), err_msg, "Error in {}", source);
+    }
+
+    // Execution stops at a runtime error, what ran before it remains.
+    assert_eq!(lox.get_global("a"), Some(Value::Number(1.0)));
+    assert!(lox.eval("var b = nil; b.c; var d = 2;").is_err());
+    assert_eq!(lox.get_global("b"), Some(Value::Nil));
+    assert_eq!(lox.get_global("d"), None);
+
+    // Errors are not printed.
+    assert!(extract_output_lines(lox.interpreter()).is_empty());
+}
+
+#[test]
+fn test_lox_run_file() {
+    let mut lox = Lox::with_output(Cursor::new(Vec::new()));
+    lox.set_global("name", Value::String("Lox".to_string()));
+
+    let value = lox.run_file("./tests/data/embedding/greeting.lox").unwrap();
+    assert_eq!(value, Value::String("Goodbye, Lox!".to_string()));
+    assert_eq!(extract_output_lines(lox.interpreter()), vec!["Hello, Lox!"]);
+
+    // Functions the file defines remain.
+    assert_eq!(lox.eval("greet(\"Hi\");").unwrap(), Value::String("Hi, Lox!".to_string()));
+
+    let err = lox.run_file("./tests/data/embedding/missing.lox").expect_err("Expected an error");
+    assert_eq!(err.phase(), Some(Phase::Io));
+}
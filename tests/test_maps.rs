@@ -0,0 +1,160 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/map/`.
+//!
+//! Maps are not in the book. Tests cover map literals, subscript get and set,
+//! key validation, and the native map methods `keys`, `values`, `has`, `remove`
+//! and `len`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_maps
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_maps_parser_error -- --exact [--nocapture]
+//!     * cargo test test_maps_interpreter -- --exact [--nocapture]
+//!
+
+mod test_common;
+use rlox::parser::Parser;
+use rlox::resolver::Resolver;
+use crate::test_common::{
+    make_interpreter_byte_stream,
+    assert_scan_script,
+    assert_parse_script_statements,
+    TestScriptAndResult,
+    TestScriptAndResults,
+    assert_parser_result,
+    assert_interpreter_result,
+};
+
+fn get_parser_error_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/map/missing_colon.lox",
+            expected_result: false,
+            expected_output: vec![
+                "[line 1] Error at '1': Expect ':' after map key.",
+                "[line 2] Error at ';': Expect '}' after map entries.",
+            ],
+        },
+    ]
+} // cargo test test_maps_parser_error -- --exact [--nocapture]
+
+fn get_interpreter_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/map/literal.lox",
+            expected_result: true,
+            expected_output: vec!["{}", "{a: 1.0, b: [true, nil]}", "{a: 2.0}", 
+                "{1.0: one, true: {x: 0.0}}", "{}"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/index.lox",
+            expected_result: true,
+            expected_output: vec!["1.0", "2.0", "{one: uno, two: 2.0}", "uno"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/keys.lox",
+            expected_result: true,
+            expected_output: vec!["number", "string", "boolean", "zero", "4.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/methods.lox",
+            expected_result: true,
+            expected_output: vec!["3.0", "[a, b, c]", "[1.0, 2.0, 3.0]", "true", 
+                "2.0", "false", "{a: 1.0, c: 3.0}", "[a, c, b]", "3.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/shared.lox",
+            expected_result: true,
+            expected_output: vec!["{k: v}", "true", "false"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/cyclic.lox",
+            expected_result: true,
+            expected_output: vec!["{k: 1.0, self: {...}}", "[{k: 1.0, self: {...}, xs: [...]}]"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/undefined_key.lox",
+            expected_result: false,
+            expected_output: vec![
+                "[line 2] Error at ']': Undefined key 'b'.",
+                "[line 3] Error at 'remove': Undefined key 'b'.",
+                "[line 4] Error at ']': Undefined key '1.0'.",
+                "[line 5] Error at 'remove': Undefined key '2.0'.",
+            ],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/invalid_key.lox",
+            expected_result: false,
+            expected_output: vec![
+                "[line 4] Error at ']': Map keys must be strings, numbers or booleans.",
+                "[line 5] Error at ']': Map keys must be strings, numbers or booleans.",
+                "[line 6] Error at '}': Map keys must be strings, numbers or booleans.",
+                "[line 7] Error at 'has': Map keys must be strings, numbers or booleans.",
+            ],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/nan_key.lox",
+            expected_result: false,
+            expected_output: vec![
+                "[line 2] Error at ']': Map keys can't be NaN.",
+                "[line 3] Error at ']': Map keys can't be NaN.",
+                "[line 4] Error at '}': Map keys can't be NaN.",
+                "[line 5] Error at 'has': Map keys can't be NaN.",
+            ],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/map/undefined_method.lox",
+            expected_result: false,
+            expected_output: vec!["[line 2] Error at 'clear': Undefined property 'clear'."],
+        },
+    ]
+} // cargo test test_maps_interpreter -- --exact [--nocapture]
+
+#[test]
+fn test_maps_parser_error() {
+    let parser_error_script_results = get_parser_error_script_results();
+
+    for entry in parser_error_script_results {
+        // Ensure script is loaded and scanned successfully.
+        let tokens = assert_scan_script(entry.script_name);
+
+        // Parsing test.
+        let mut parser = Parser::new(&tokens);
+        let res = parser.parse();
+
+        assert_parser_result(&entry, &res);
+    }
+}
+
+#[test]
+fn test_maps_interpreter() {
+    let script_results = get_interpreter_script_results();
+
+    // Resolver needs an mutable Interpreter instance.
+    let mut interpreter = make_interpreter_byte_stream();
+
+    for entry in script_results {
+        interpreter.reset(true);
+
+        // Ensure script is loaded, scanned and parsed successfully.
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        // Create a resolver instance for each script file.
+        let mut resolver: Resolver = Resolver::new(&mut interpreter);
+
+        // Resolver test.
+        let res = resolver.resolve(&statements);
+
+        // Ensure resolving is successful.
+        assert!(res.is_ok(), "resolve error: {}", entry.script_name);
+
+        // Test interpreting/evaluating.
+        let res = interpreter.interpret(&statements);
+
+        assert_interpreter_result(&entry, &res, &interpreter);
+    }
+}
@@ -0,0 +1,169 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/import/`.
+//!
+//! `import` is not in the book. Tests cover the parser and the interpreter, 
+//! and the stack traces on both backends. 
+//! Each script sets [`rlox::interpreter::Interpreter::set_script_path`], so 
+//! that its imports resolve against `./data/import/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_modules
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_modules_parser_error -- --exact [--nocapture]
+//!     * cargo test test_modules_interpreter -- --exact [--nocapture]
+//!     * cargo test test_modules_stack_trace -- --exact [--nocapture]
+//!
+
+mod test_common;
+use rlox::parser::Parser;
+use rlox::resolver::Resolver;
+use crate::test_common::{
+    make_interpreter_byte_stream,
+    make_vm_byte_stream,
+    assert_scan_script,
+    assert_parse_script_statements,
+    TestScriptAndResult,
+    TestScriptAndResults,
+    assert_parser_result,
+    assert_interpreter_result,
+};
+
+fn get_parser_error_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/import/invalid_name.lox",
+            expected_result: false,
+            expected_output: vec!["[line 1] Error at '\"lib/my-lib.lox\"': Can't name module 'lib/my-lib.lox', use 'as' to name it."],
+        },
+    ]
+} // cargo test test_modules_parser_error -- --exact [--nocapture]
+
+fn get_interpreter_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/import/basic.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["math loaded", "9.0", "3.14", "<module math>"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/import/alias.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["math loaded", "16.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/import/once.lox",
+            expected_result: true,
+            expected_output: vec!["math loaded", "true"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/import/module_globals.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["2.0", "2.0", "100.0"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/import/nested.lox",
+            expected_result: true,
+            expected_output: vec!["math loaded", "12.56"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/import/cycle.lox",
+            expected_result: false,
+            expected_output: vec!["[line 1] Error at 'import': Import cycle: cycle.lox -> cycle_b.lox -> cycle.lox."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/import/missing.lox",
+            expected_result: false,
+            expected_output: vec!["[line 1] Error at 'import': Can't open module 'lib/nope.lox'."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/import/broken_module.lox",
+            expected_result: false,
+            expected_output: vec!["[line 1] Error at 'import': Error in module 'lib/broken.lox': [line 1] Error at '=': Expect variable name."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/import/undefined_member.lox",
+            expected_result: false,
+            expected_output: vec!["[line 2] Error at 'decrement': Undefined property 'decrement'."],
+        },
+    ]
+} // cargo test test_modules_interpreter -- --exact [--nocapture]
+
+#[test]
+fn test_modules_parser_error() {
+    let parser_error_script_results = get_parser_error_script_results();
+
+    for entry in parser_error_script_results {
+        // Ensure script is loaded and scanned successfully.
+        let tokens = assert_scan_script(entry.script_name);
+
+        // Parsing test.
+        let mut parser = Parser::new(&tokens);
+        let res = parser.parse();
+
+        assert_parser_result(&entry, &res);
+    }
+}
+
+#[test]
+fn test_modules_interpreter() {
+    let script_results = get_interpreter_script_results();
+
+    // Resolver needs an mutable Interpreter instance.
+    let mut interpreter = make_interpreter_byte_stream();
+
+    for entry in script_results {
+        interpreter.reset(true);
+        // Imports resolve against the script's directory.
+        interpreter.set_script_path(entry.script_name);
+
+        // Ensure script is loaded, scanned and parsed successfully.
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        // Create a resolver instance for each script file.
+        let mut resolver: Resolver = Resolver::new(&mut interpreter);
+
+        // Resolver test.
+        let res = resolver.resolve(&statements);
+
+        // Ensure resolving is successful.
+        assert!(res.is_ok(), "resolve error: {}", entry.script_name);
+
+        // Test interpreting/evaluating.
+        let res = interpreter.interpret(&statements);
+
+        assert_interpreter_result(&entry, &res, &interpreter);
+    }
+}
+
+#[test]
+// Module frames are labelled with the module's path, and the importing frame 
+// is at its `import` statement.
+fn test_modules_stack_trace() {
+    for (script_name, expected) in [
+        ("./tests/data/import/cycle.lox", 
+            vec!["[line 1] in module 'lib/cycle_b.lox'", "[line 1] in script"]),
+        ("./tests/data/import/module_error.lox", 
+            vec!["[line 3] in module 'lib/faulty_top.lox'", "[line 2] in script"]),
+    ] {
+        let statements = assert_parse_script_statements(script_name);
+
+        let mut interpreter = make_interpreter_byte_stream();
+        interpreter.set_script_path(script_name);
+        let mut resolver: Resolver = Resolver::new(&mut interpreter);
+        assert!(resolver.resolve(&statements).is_ok(), "resolve error: {}", script_name);
+        let err = interpreter.interpret(&statements).expect_err("Expected a runtime error");
+        assert_eq!(err.errors()[0].trace(), expected, "Error in {}", script_name);
+
+        let mut vm = make_vm_byte_stream();
+        vm.set_script_path(script_name);
+        let err = vm.interpret(&statements).expect_err("Expected a runtime error");
+        assert_eq!(err.errors()[0].trace(), expected, "Error in {}", script_name);
+    }
+}
@@ -0,0 +1,127 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/native/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_natives
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_natives_calls -- --exact [--nocapture]
+//!     * cargo test test_natives_errors -- --exact [--nocapture]
+//!     * cargo test test_natives_names -- --exact [--nocapture]
+//!
+
+mod test_common;
+
+use rlox::interpreter::Interpreter;
+use rlox::lox_error::LoxError;
+use rlox::lox_error_helper::sys_error;
+use rlox::lox_native::Arity;
+use rlox::lox_runtime_error::LoxRuntimeError;
+use rlox::resolver::Resolver;
+use rlox::value::Value;
+use crate::test_common::{
+    assert_parse_script_statements,
+    make_interpreter_byte_stream,
+    make_vm_byte_stream,
+    extract_output_lines,
+    extract_vm_output_lines,
+    extract_error_lines,
+};
+
+fn make_interpreter_with_natives() -> Interpreter {
+    let mut interpreter = make_interpreter_byte_stream();
+
+    interpreter.define_native("sum", Arity::Variadic, |_, arguments| {
+        let mut sum = 0.0;
+        for argument in arguments {
+            match argument {
+                Value::Number(n) => sum += n,
+                _ => return Err(sys_error("", "Operands must be numbers.").into()),
+            }
+        }
+        Ok(Value::Number(sum))
+    });
+
+    interpreter.define_native("greet", Arity::Fixed(1), |_, arguments| {
+        Ok(Value::String(format!("Hello, {}!", arguments[0])))
+    });
+
+    interpreter.define_native("positive", Arity::Fixed(1), |_, arguments| {
+        match arguments[0] {
+            Value::Number(n) if n > 0.0 => Ok(Value::Number(n)),
+            _ => Err(LoxRuntimeError::Error(sys_error("", "Expected a positive number."))),
+        }
+    });
+
+    interpreter
+}
+
+// Runs the script, returns its output, its error output and its error, if any.
+fn run_script(script_name: &str) -> (Vec<String>, Vec<String>, Option<LoxError>) {
+    let statements = assert_parse_script_statements(script_name);
+
+    let mut interpreter = make_interpreter_with_natives();
+    let mut resolver = Resolver::new(&mut interpreter);
+    assert!(resolver.resolve(&statements).is_ok(), "Error in {}", script_name);
+
+    let result = interpreter.interpret(&statements);
+    (extract_output_lines(&interpreter), extract_error_lines(&result), result.err())
+}
+
+#[test]
+fn test_natives_calls() {
+    let (output, errors, err) = run_script("./tests/data/native/natives.lox");
+
+    assert!(err.is_none());
+    assert!(errors.is_empty());
+    assert_eq!(output, vec!["6.0", "0.0", "Hello, Lox!", "<native fn greet>", "<native fn sum>"]);
+}
+
+#[test]
+fn test_natives_errors() {
+    // Reported at the call, with the native's frame in the stack trace.
+    let (output, error_lines, err) = run_script("./tests/data/native/errors.lox");
+
+    let err = err.expect("Expected a runtime error");
+    let errors = err.errors();
+    assert_eq!(output, vec!["1.0"]);
+    assert_eq!(error_lines, vec!["[line 2] Error at ')': Expected a positive number."]);
+    assert_eq!(errors[0].span().column, 24);
+    assert_eq!(errors[0].trace(), vec![
+        "[native] in positive()",
+        "[line 2] in check()",
+        "[line 6] in script",
+    ]);
+
+    let (output, error_lines, err) = run_script("./tests/data/native/arity.lox");
+
+    assert!(err.is_some());
+    assert!(output.is_empty());
+    assert_eq!(error_lines, vec!["[line 1] Error at ')': Expected 1 arguments but got 2."]);
+}
+
+#[test]
+// The built-in natives print their names too, on both backends.
+fn test_natives_names() {
+    let script_name = "./tests/data/native/names.lox";
+    let expected = vec![
+        "<native fn clock>",
+        "<native fn push>",
+        "<native fn remove>",
+        "<native fn keys>",
+        "<native fn has>",
+    ];
+
+    let (output, errors, err) = run_script(script_name);
+    assert!(err.is_none());
+    assert!(errors.is_empty());
+    assert_eq!(output, expected);
+
+    let statements = assert_parse_script_statements(script_name);
+    let mut vm = make_vm_byte_stream();
+    assert!(vm.interpret(&statements).is_ok());
+    assert_eq!(extract_vm_output_lines(&vm), expected);
+}
@@ -24,6 +24,8 @@
 //!     * cargo test test_parser_classes_this -- --exact [--nocapture]
 //!     * cargo test test_parser_inheritance_super_sub_class -- --exact [--nocapture]
 //!     * cargo test test_parser_inheritance_calling_superclass_method -- --exact [--nocapture]
+//!     * cargo test test_parser_spans -- --exact [--nocapture]
+//!     * cargo test test_parser_doc_comments -- --exact [--nocapture]
 //! 
 
 use std::rc::Rc;
@@ -37,7 +39,8 @@ use crate::test_common::{
     assert_parser_result,
 };
 
-use rlox::token::Token;
+use rlox::token::{Span, Token};
+use rlox::stmt::Stmt;
 use rlox::parser::Parser;
 use rlox::ast_printer::AstPrinter;
 
@@ -615,3 +618,69 @@ fn test_parser_inheritance_calling_superclass_method() {
         assert_parser_result(&entry, &res);
    }
 }
+
+#[test]
+// Nodes span from their first token to their last.
+fn test_parser_spans() {
+    let tokens = assert_scan_script("./tests/data/scanning/spans.lox");
+
+    let mut parser = make_parser(&tokens);
+    let res = parser.parse();
+    assert!(res.is_ok(), "Parse error: {:?}", res.err());
+
+    let statements = res.unwrap();
+    assert_eq!(statements.len(), 2);
+
+    // var greeting = "héllo";
+    assert_eq!(statements[0].span(), 
+        &Span { line: 1, column: 1, end_line: 1, end_column: 24, start: 0, end: 24 });
+
+    // print greeting +
+    //   "wörld";
+    assert_eq!(statements[1].span(), 
+        &Span { line: 2, column: 1, end_line: 3, end_column: 11, start: 25, end: 53 });
+
+    match statements[1].as_ref() {
+        Stmt::Print(print) => assert_eq!(print.expression().span(),
+            &Span { line: 2, column: 7, end_line: 3, end_column: 10, start: 31, end: 52 }),
+        _ => panic!("Expected a print statement"),
+    }
+}
+
+#[test]
+// The names of the declarations have their doc comments, methods included.
+fn test_parser_doc_comments() {
+    let tokens = assert_scan_script("./tests/data/scanning/doc_comments.lox");
+
+    let mut parser = make_parser(&tokens);
+    let res = parser.parse();
+    assert!(res.is_ok(), "Parse error: {:?}", res.err());
+
+    let mut docs: Vec<(String, Option<String>)> = vec![];
+    let mut add_doc = |name: &Token| 
+        docs.push((name.lexeme().to_string(), name.doc().map(str::to_string)));
+    for stmt in res.unwrap() {
+        match stmt.as_ref() {
+            Stmt::Function(function) => add_doc(function.name()),
+            Stmt::Var(var) => add_doc(var.name()),
+            Stmt::Class(class) => {
+                add_doc(class.name());
+                for method in class.methods() {
+                    add_doc(method.name());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let doc = |doc: &str| Some(doc.to_string());
+    assert_eq!(docs, vec![
+        ("add".to_string(), doc("Adds two numbers.\nReturns their sum.")),
+        ("Point".to_string(), doc("A point.")),
+        ("answer".to_string(), doc("The answer.")),
+        ("plain".to_string(), None),
+        ("Counter".to_string(), doc("A counter.")),
+        ("add".to_string(), doc("Adds one.")),
+        ("reset".to_string(), None),
+    ]);
+}
@@ -0,0 +1,182 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/sandbox/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_sandbox
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_sandbox_step_budget -- --exact [--nocapture]
+//!     * cargo test test_sandbox_timeout -- --exact [--nocapture]
+//!     * cargo test test_sandbox_cancel -- --exact [--nocapture]
+//!     * cargo test test_sandbox_cancel_host_call -- --exact [--nocapture]
+//!     * cargo test test_sandbox_allocation_budget -- --exact [--nocapture]
+//!
+
+mod test_common;
+
+use std::io::Cursor;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rlox::Lox;
+use rlox::cancel_token::CancelToken;
+use rlox::interpreter::Interpreter;
+use rlox::lox_error::LoxError;
+use rlox::resolver::Resolver;
+use rlox::value::Value;
+use crate::test_common::{
+    assert_parse_script_statements,
+    make_interpreter_byte_stream,
+    extract_output_lines,
+};
+
+// Runs the script, returns its output and its error, if any.
+fn run_script(script_name: &str, mut interpreter: Interpreter) -> (Vec<String>, Option<LoxError>) {
+    let statements = assert_parse_script_statements(script_name);
+
+    let mut resolver = Resolver::new(&mut interpreter);
+    assert!(resolver.resolve(&statements).is_ok(), "Error in {}", script_name);
+
+    let result = interpreter.interpret(&statements);
+    (extract_output_lines(&interpreter), result.err())
+}
+
+#[test]
+fn test_sandbox_step_budget() {
+    let (output, err) = run_script("./tests/data/sandbox/counting.lox",
+        make_interpreter_byte_stream().with_step_budget(1000));
+    assert!(err.is_none());
+    assert_eq!(output, vec!["1.0", "2.0", "3.0"]);
+
+    // The error stops the whole script.
+    let (output, err) = run_script("./tests/data/sandbox/infinite_loop.lox",
+        make_interpreter_byte_stream().with_step_budget(1000));
+    let err = err.expect("Expected a runtime error");
+    assert_eq!(err.errors().len(), 1);
+    assert_eq!(err.errors()[0].err_msg(), "Step budget exceeded.");
+    assert_eq!(err.errors()[0].trace(), vec!["[line 2] in script"]);
+    assert_eq!(output, vec!["start"]);
+
+    // Neither `catch` nor `finally` run.
+    let (output, err) = run_script("./tests/data/sandbox/caught.lox",
+        make_interpreter_byte_stream().with_step_budget(1000));
+    assert!(err.is_some());
+    assert!(output.is_empty());
+}
+
+#[test]
+fn test_sandbox_timeout() {
+    let start = Instant::now();
+    let (output, err) = run_script("./tests/data/sandbox/spin.lox",
+        make_interpreter_byte_stream().with_timeout(Duration::from_millis(50)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let err = err.expect("Expected a runtime error");
+    assert_eq!(err.errors()[0].err_msg(), "Script timed out.");
+    assert_eq!(err.errors()[0].trace(), vec!["[line 2] in spin()", "[line 5] in script"]);
+    assert!(output.is_empty());
+
+    // A deadline already passed stops the first loop iteration.
+    let (output, err) = run_script("./tests/data/sandbox/counting.lox",
+        make_interpreter_byte_stream().with_deadline(Instant::now()));
+    assert!(err.is_some());
+    assert_eq!(output, vec!["1.0"]);
+}
+
+#[test]
+fn test_sandbox_cancel() {
+    let token = CancelToken::new();
+    let mut lox = Lox::with_output(Cursor::new(Vec::new())).with_cancel_token(token.clone());
+
+    let handle = token.clone();
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        handle.cancel();
+    });
+
+    let err = lox.run_file("./tests/data/sandbox/cancel.lox").expect_err("Expected a runtime error");
+    canceller.join().unwrap();
+    assert_eq!(err.err_msg(), "Script cancelled.");
+    assert_eq!(err.trace(), vec!["[line 4] in script"]);
+
+    // The block's scope is gone: new variables are globals again.
+    token.reset();
+    lox.eval("var after = 3;").unwrap();
+    assert_eq!(lox.get_global("before"), Some(Value::Number(1.0)));
+    assert_eq!(lox.get_global("after"), Some(Value::Number(3.0)));
+    assert_eq!(lox.get_global("inner"), None);
+}
+
+#[test]
+// A callback which the host calls stops before it runs, as a call in the 
+// script would.
+fn test_sandbox_cancel_host_call() {
+    let token = CancelToken::new();
+    let mut lox = Lox::with_output(Cursor::new(Vec::new())).with_cancel_token(token.clone());
+    lox.eval("var calls = 0; fun callback() { calls = calls + 1; } class Handler { run() { callback(); } }")
+        .unwrap();
+    let callback = lox.get_global("callback").unwrap();
+    let handler = lox.eval("Handler();").unwrap();
+
+    token.cancel();
+    let err = lox.call_value(&callback, vec![]).expect_err("Expected a runtime error");
+    assert_eq!(err.err_msg(), "Script cancelled.");
+    let err = lox.call_method(&handler, "run", vec![]).expect_err("Expected a runtime error");
+    assert_eq!(err.err_msg(), "Script cancelled.");
+    assert_eq!(lox.get_global("calls"), Some(Value::Number(0.0)));
+
+    token.reset();
+    lox.call_value(&callback, vec![]).unwrap();
+    lox.call_method(&handler, "run", vec![]).unwrap();
+    assert_eq!(lox.get_global("calls"), Some(Value::Number(2.0)));
+
+    // The call depth is checked too.
+    lox.set_max_call_depth(1);
+    let err = lox.call_value(&callback, vec![]).expect_err("Expected a runtime error");
+    assert_eq!(err.err_msg(), "Stack overflow.");
+}
+
+#[test]
+fn test_sandbox_allocation_budget() {
+    let (output, err) = run_script("./tests/data/sandbox/counting.lox",
+        make_interpreter_byte_stream().with_allocation_budget(1024));
+    assert!(err.is_none());
+    assert_eq!(output, vec!["1.0", "2.0", "3.0"]);
+
+    let (output, err) = run_script("./tests/data/sandbox/doubling.lox",
+        make_interpreter_byte_stream().with_allocation_budget(1024 * 1024));
+    let err = err.expect("Expected a runtime error");
+    assert_eq!(err.errors()[0].err_msg(), "Allocation budget exceeded.");
+    assert_eq!(err.errors()[0].trace(), vec!["[line 3] in script"]);
+    assert!(output.is_empty());
+
+    // Assigning an existing field is not counted.
+    let (output, err) = run_script("./tests/data/sandbox/fields.lox",
+        make_interpreter_byte_stream().with_allocation_budget(1024));
+    assert!(err.is_none());
+    assert_eq!(output, vec!["9999.0"]);
+
+    // Stops well before the call depth limit.
+    let (output, err) = run_script("./tests/data/sandbox/deep.lox",
+        make_interpreter_byte_stream().with_allocation_budget(1024));
+    let err = err.expect("Expected a runtime error");
+    assert!(err.errors()[0].trace().len() < 64);
+    assert!(output.is_empty());
+
+    let (output, err) = run_script("./tests/data/sandbox/growing_list.lox",
+        make_interpreter_byte_stream().with_allocation_budget(10 * 1024));
+    let err = err.expect("Expected a runtime error");
+    assert_eq!(err.errors()[0].err_msg(), "Allocation budget exceeded.");
+    assert_eq!(err.errors()[0].trace(), vec!["[native] in push()", "[line 4] in script"]);
+    assert!(output.is_empty());
+
+    let (output, err) = run_script("./tests/data/sandbox/growing_map.lox",
+        make_interpreter_byte_stream().with_allocation_budget(10 * 1024));
+    let err = err.expect("Expected a runtime error");
+    assert_eq!(err.errors()[0].err_msg(), "Allocation budget exceeded.");
+    assert_eq!(err.errors()[0].trace(), vec!["[line 9] in script"]);
+    assert_eq!(output, vec!["9999.0"]);
+}
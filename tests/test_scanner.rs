@@ -16,6 +16,15 @@
 //!     * cargo test test_scanner_whitespace -- --exact [--nocapture]
 //!     * cargo test test_scanner_sample -- --exact [--nocapture]
 //!     * cargo test test_scanner_utf8_text -- --exact [--nocapture]
+//!     * cargo test test_scanner_spans -- --exact [--nocapture]
+//!     * cargo test test_scanner_large_source -- --exact [--nocapture]
+//!     * cargo test test_scanner_recovery -- --exact [--nocapture]
+//!     * cargo test test_scanner_leading_newlines -- --exact [--nocapture]
+//!     * cargo test test_scanner_crlf -- --exact [--nocapture]
+//!     * cargo test test_scanner_block_comments -- --exact [--nocapture]
+//!     * cargo test test_scanner_doc_comments -- --exact [--nocapture]
+//!     * cargo test test_scanner_escapes -- --exact [--nocapture]
+//!     * cargo test test_scanner_invalid_escapes -- --exact [--nocapture]
 //! 
 //!     * cargo test test_scanner_generics -- --exact [--nocapture]
 //! 
@@ -33,7 +42,9 @@ use crate::test_common::{
 };
 
 use rlox::token_type::TokenType;
+use rlox::token::Span;
 use rlox::scanner::Scanner;
+use rlox::parser::Parser;
 
 #[test]
 fn test_scanner_identifiers() {
@@ -101,8 +112,8 @@ fn test_scanner_identifiers() {
     assert_eq!(token.token_type(), TokenType::Eof);
     assert_eq!(token.lexeme(), "");
     assert_literal_none(token.literal());
-    // 13 is correct. There are 13 lines: but blank line is trimmed off.
-    assert_eq!(token.line(), 12);
+    // The source ends with a newline: Eof is on the blank 13th line.
+    assert_eq!(token.line(), 13);
 }
 
 #[test]
@@ -213,8 +224,8 @@ fn test_scanner_keywords() {
     assert_eq!(token.token_type(), TokenType::Eof);
     assert_eq!(token.lexeme(), "");
     assert_literal_none(token.literal());
-    // There are 19 lines in the script file: but blank line is trimmed off.
-    assert_eq!(token.line(), 18);
+    // There are 19 lines in the script file, the last one is blank.
+    assert_eq!(token.line(), 19);
 }
 
 #[test]
@@ -275,8 +286,8 @@ fn test_scanner_numbers() {
     assert_eq!(token.token_type(), TokenType::Eof);
     assert_eq!(token.lexeme(), "");
     assert_literal_none(token.literal());
-    // There are 13 lines in the script file: but blank line is trimmed off.
-    assert_eq!(token.line(), 12);
+    // There are 13 lines in the script file, the last one is blank.
+    assert_eq!(token.line(), 13);
 }
 
 #[test]
@@ -405,8 +416,8 @@ fn test_scanner_punctuators() {
     assert_eq!(token.token_type(), TokenType::Eof);
     assert_eq!(token.lexeme(), "");
     assert_literal_none(token.literal());
-    // The script file has 22 lines: but blank line is trimmed off.
-    assert_eq!(token.line(), 21);
+    // The script file has 22 lines, the last one is blank.
+    assert_eq!(token.line(), 22);
 }
 
 #[test]
@@ -487,8 +498,8 @@ fn test_scanner_whitespace() {
     assert_eq!(token.token_type(), TokenType::Eof);
     assert_eq!(token.lexeme(), "");
     assert_literal_none(token.literal());
-    //The blank line is trimmed off.
-    assert_eq!(token.line(), 12);
+    // The last line is blank.
+    assert_eq!(token.line(), 13);
 }
 
 #[test]
@@ -656,8 +667,8 @@ fn test_scanner_utf8_text() {
     assert_eq!(token.token_type(), TokenType::Eof);
     assert_eq!(token.lexeme(), "");
     assert_literal_none(token.literal());
-    // 24 lines in the script file: but blank line is trimmed off.
-    assert_eq!(token.line(), 23);
+    // 24 lines in the script file, the last one is blank.
+    assert_eq!(token.line(), 24);
 }
 
 fn get_generic_script_results<'a>() -> TestScriptAndResults<'a> {    
@@ -668,6 +679,12 @@ fn get_generic_script_results<'a>() -> TestScriptAndResults<'a> {
             expected_result: false,
             expected_output: vec!["[line 2] Error at '\0': Unterminated string."],
         },
+        // At the opening /*, not at the end of the source.
+        TestScriptAndResult {
+            script_name: "./tests/data/scanning/unterminated_block_comment.lox",
+            expected_result: false,
+            expected_output: vec!["[line 2] Error at '/*': Unterminated block comment."],
+        },
         // Author's https://github.com/munificent/craftinginterpreters/tree/master/test/
         //
         // I have missed these root master/test/ author-provided scripts till after 
@@ -705,6 +722,284 @@ fn get_generic_script_results<'a>() -> TestScriptAndResults<'a> {
     ] // cargo test test_scanner_generics -- --exact
 }
 
+#[test]
+// Columns count characters and byte offsets count bytes: 'é' and 'ö' are 
+// two bytes each.
+fn test_scanner_spans() {
+    let res = get_script_contents("./tests/data/scanning/spans.lox");
+    // Read script file was successful.
+    assert_eq!(res.is_err(), false);
+
+    let source = res.unwrap();
+    let res = Scanner::new(&source).scan_tokens();
+    // Scanning was successful.
+    assert_eq!(res.is_err(), false);
+
+    let token_list = res.unwrap();
+
+    assert_eq!(token_list.len(), 11);
+
+    let expected = [
+        (TokenType::Var, Span { line: 1, column: 1, end_line: 1, end_column: 4, start: 0, end: 3 }),
+        (TokenType::Identifier, Span { line: 1, column: 5, end_line: 1, end_column: 13, start: 4, end: 12 }),
+        (TokenType::Equal, Span { line: 1, column: 14, end_line: 1, end_column: 15, start: 13, end: 14 }),
+        (TokenType::String, Span { line: 1, column: 16, end_line: 1, end_column: 23, start: 15, end: 23 }),
+        (TokenType::Semicolon, Span { line: 1, column: 23, end_line: 1, end_column: 24, start: 23, end: 24 }),
+        (TokenType::Print, Span { line: 2, column: 1, end_line: 2, end_column: 6, start: 25, end: 30 }),
+        (TokenType::Identifier, Span { line: 2, column: 7, end_line: 2, end_column: 15, start: 31, end: 39 }),
+        (TokenType::Plus, Span { line: 2, column: 16, end_line: 2, end_column: 17, start: 40, end: 41 }),
+        (TokenType::String, Span { line: 3, column: 3, end_line: 3, end_column: 10, start: 44, end: 52 }),
+        (TokenType::Semicolon, Span { line: 3, column: 10, end_line: 3, end_column: 11, start: 52, end: 53 }),
+        // An empty span at the end of the source, after its last newline.
+        (TokenType::Eof, Span { line: 4, column: 1, end_line: 4, end_column: 1, start: 54, end: 54 }),
+    ];
+
+    for (token, (token_type, span)) in token_list.iter().zip(expected.iter()) {
+        assert_eq!(token.token_type(), *token_type);
+        assert_eq!(token.span(), span);
+        // The span is the lexeme.
+        assert_eq!(&source[span.start..span.end], token.lexeme());
+    }
+}
+
+#[test]
+// About 1MB: scanning takes linear time. Spans stay exact to the end.
+fn test_scanner_large_source() {
+    let res = get_script_contents("./tests/data/scanning/spans.lox");
+    assert_eq!(res.is_err(), false);
+
+    let chunk = format!("{}\n", res.unwrap().trim_end());
+    let count = 1024 * 1024 / chunk.len();
+    let source = chunk.repeat(count);
+
+    let res = Scanner::new(&source).scan_tokens();
+    assert_eq!(res.is_err(), false);
+
+    let token_list = res.unwrap();
+    assert_eq!(token_list.len(), 10 * count + 1);
+
+    // The last `;`.
+    let token = &token_list[token_list.len() - 2];
+    assert_eq!(token.token_type(), TokenType::Semicolon);
+    assert_eq!(token.span(), &Span { line: 3 * count, column: 10, end_line: 3 * count, end_column: 11, 
+        start: source.len() - 2, end: source.len() - 1 });
+}
+
+#[test]
+// All errors are reported, and there are tokens for everything else.
+fn test_scanner_recovery() {
+    let res = get_script_contents("./tests/data/scanning/recovery.lox");
+    assert_eq!(res.is_err(), false);
+
+    let (token_list, err) = Scanner::new(&res.unwrap()).scan_tokens_with_errors();
+
+    let err_msgs: Vec<String> = err.expect("Expected scanner errors").to_string()
+        .lines()
+        .map(|line| line.to_string())
+        .collect();
+    assert_eq!(err_msgs, vec![
+        "[line 1] Error at '|': Unexpected character: |.",
+        "[line 3] Error at '?': Unexpected character: ?.",
+        "[line 4] Error at '\0': Unterminated string.",
+    ]);
+
+    let token_types: Vec<TokenType> = token_list.iter().map(|token| token.token_type()).collect();
+    assert_eq!(token_types, vec![
+        TokenType::Var, TokenType::Identifier, TokenType::Equal, TokenType::Number, 
+        TokenType::Number, TokenType::Semicolon,
+        TokenType::Print, TokenType::Identifier, TokenType::Semicolon,
+        TokenType::Print, TokenType::Identifier, TokenType::String, TokenType::Semicolon,
+        TokenType::Print, 
+        TokenType::Eof,
+    ]);
+    assert_eq!(token_list[4].line(), 1);
+    assert_eq!(token_list[13].line(), 4);
+}
+
+#[test]
+// The source is not trimmed: lines are those shown in an editor.
+fn test_scanner_leading_newlines() {
+    let res = get_script_contents("./tests/data/scanning/leading_newlines.lox");
+    assert_eq!(res.is_err(), false);
+
+    let source = res.unwrap();
+    let res = Scanner::new(&source).scan_tokens();
+    assert_eq!(res.is_err(), false);
+
+    let token_list = res.unwrap();
+    assert_eq!(token_list.len(), 9);
+
+    let start = source.find("var").unwrap();
+    assert_eq!(token_list[0].token_type(), TokenType::Var);
+    assert_eq!(token_list[0].line(), 5);
+    assert_eq!(token_list[0].span(), &Span { line: 5, column: 1, end_line: 5, end_column: 4, 
+        start, end: start + 3 });
+    assert_eq!(token_list[5].token_type(), TokenType::Print);
+    assert_eq!(token_list[5].line(), 6);
+    // After the last newline.
+    assert_eq!(token_list[8].token_type(), TokenType::Eof);
+    assert_eq!(token_list[8].line(), 7);
+
+    // Errors too.
+    let res = Scanner::new("\n\n  print @;\n").scan_tokens();
+    let err = res.expect_err("Expected a scanner error");
+    assert_eq!(err.to_string(), "[line 3] Error at '@': Unexpected character: @.");
+    assert_eq!(err.errors()[0].span().column, 9);
+}
+
+#[test]
+// '\r' is whitespace: CRLF line endings give the same lines and columns as LF.
+fn test_scanner_crlf() {
+    let res = get_script_contents("./tests/data/scanning/crlf.lox");
+    assert_eq!(res.is_err(), false);
+
+    let source = res.unwrap();
+    assert!(source.contains("\r\n"), "crlf.lox must keep its CRLF line endings");
+
+    let res = Scanner::new(&source).scan_tokens();
+    assert_eq!(res.is_err(), false);
+
+    let token_list = res.unwrap();
+    assert_eq!(token_list.len(), 12);
+
+    let expected = [
+        (TokenType::Var, Span { line: 1, column: 1, end_line: 1, end_column: 4, start: 0, end: 3 }),
+        (TokenType::Semicolon, Span { line: 1, column: 20, end_line: 1, end_column: 21, start: 19, end: 20 }),
+        (TokenType::Print, Span { line: 2, column: 1, end_line: 2, end_column: 6, start: 22, end: 27 }),
+        (TokenType::Identifier, Span { line: 2, column: 7, end_line: 2, end_column: 15, start: 28, end: 36 }),
+        (TokenType::Print, Span { line: 3, column: 1, end_line: 3, end_column: 6, start: 39, end: 44 }),
+        // Over two lines.
+        (TokenType::String, Span { line: 3, column: 7, end_line: 4, end_column: 7, start: 45, end: 57 }),
+        (TokenType::Semicolon, Span { line: 4, column: 7, end_line: 4, end_column: 8, start: 57, end: 58 }),
+        (TokenType::Eof, Span { line: 5, column: 1, end_line: 5, end_column: 1, start: 60, end: 60 }),
+    ];
+
+    for (token_type, span) in expected.iter() {
+        let token = token_list.iter().find(|token| token.span() == span)
+            .unwrap_or_else(|| panic!("No {:?} token at {:?}", token_type, span));
+        assert_eq!(token.token_type(), *token_type);
+        // Like jlox, a token is on the line where it ends.
+        assert_eq!(token.line(), span.end_line);
+        assert_eq!(&source[span.start..span.end], token.lexeme());
+    }
+    assert_literal_string(token_list[9].literal(), "two\r\nlines");
+}
+
+#[test]
+fn test_scanner_block_comments() {
+    let res = get_script_contents("./tests/data/scanning/block_comments.lox");
+    assert_eq!(res.is_err(), false);
+
+    let res = Scanner::new(&res.unwrap()).scan_tokens();
+    assert_eq!(res.is_err(), false);
+
+    let token_list = res.unwrap();
+    let tokens: Vec<(TokenType, usize)> = token_list.iter()
+        .map(|token| (token.token_type(), token.line()))
+        .collect();
+    assert_eq!(tokens, vec![
+        (TokenType::Print, 2), (TokenType::String, 2), (TokenType::Semicolon, 2),
+        (TokenType::Print, 6), (TokenType::String, 6), (TokenType::Semicolon, 6),
+        (TokenType::Print, 7), (TokenType::String, 7), (TokenType::Semicolon, 7),
+        (TokenType::Eof, 8),
+    ]);
+    assert_eq!(token_list[4].span().column, 20);
+}
+
+#[test]
+// Doc comments are attached to the `fun`, `class` and `var` keywords.
+fn test_scanner_doc_comments() {
+    let res = get_script_contents("./tests/data/scanning/doc_comments.lox");
+    assert_eq!(res.is_err(), false);
+
+    let res = Scanner::new(&res.unwrap()).scan_tokens();
+    assert_eq!(res.is_err(), false);
+
+    let docs: Vec<(TokenType, usize, Option<&str>)> = res.as_ref().unwrap().iter()
+        .filter(|token| matches!(token.token_type(), 
+            TokenType::Fun | TokenType::Class | TokenType::Var | TokenType::Print))
+        .map(|token| (token.token_type(), token.line(), token.doc()))
+        .collect();
+    assert_eq!(docs, vec![
+        (TokenType::Fun, 3, Some("Adds two numbers.\nReturns their sum.")),
+        (TokenType::Class, 8, Some("A point.")),
+        (TokenType::Var, 12, Some("The answer.")),
+        (TokenType::Print, 15, None),
+        (TokenType::Var, 18, None),
+        (TokenType::Class, 21, Some("A counter.")),
+    ]);
+
+    // Besides these, only the method's name has one.
+    let names: Vec<(&str, Option<&str>)> = res.as_ref().unwrap().iter()
+        .filter(|token| token.token_type() == TokenType::Identifier && token.doc().is_some())
+        .map(|token| (token.lexeme(), token.doc()))
+        .collect();
+    assert_eq!(names, vec![("add", Some("Adds one."))]);
+    assert_eq!(res.unwrap().iter().filter(|token| token.doc().is_some()).count(), 5);
+}
+
+#[test]
+// The literal has the escaped characters, the lexeme is the source text.
+fn test_scanner_escapes() {
+    let res = get_script_contents("./tests/data/scanning/escapes.lox");
+    assert_eq!(res.is_err(), false);
+
+    let source = res.unwrap();
+    let res = Scanner::new(&source).scan_tokens();
+    assert_eq!(res.is_err(), false);
+
+    let strings: Vec<_> = res.as_ref().unwrap().iter()
+        .filter(|token| token.token_type() == TokenType::String)
+        .collect();
+    assert_eq!(strings.len(), 4);
+
+    assert_literal_string(strings[0].literal(), "tab:\tend");
+    assert_literal_string(strings[1].literal(), "quote: \" backslash: \\");
+    assert_literal_string(strings[2].literal(), "snowman: \u{2603}, crab: \u{1F980}");
+    assert_literal_string(strings[3].literal(), "two\nlines\r\0");
+
+    assert_eq!(strings[1].lexeme(), r#""quote: \" backslash: \\""#);
+    for token in strings {
+        assert_eq!(&source[token.span().start..token.span().end], token.lexeme());
+    }
+}
+
+#[test]
+// Each error is at its escape sequence. Scanning carries on after the string.
+fn test_scanner_invalid_escapes() {
+    let res = get_script_contents("./tests/data/scanning/invalid_escapes.lox");
+    assert_eq!(res.is_err(), false);
+
+    let (token_list, err) = Scanner::new(&res.unwrap()).scan_tokens_with_errors();
+    let err = err.expect("Expected scanner errors");
+
+    let errors: Vec<(String, Span)> = err.errors().iter()
+        .map(|err| (err.to_string(), *err.span()))
+        .collect();
+    assert_eq!(errors, vec![
+        ("[line 2] Error at '\\q': Invalid escape sequence.".to_string(),
+            Span { line: 2, column: 13, end_line: 2, end_column: 15, start: 26, end: 28 }),
+        ("[line 3] Error at '\\u{1234567}': Invalid escape sequence.".to_string(),
+            Span { line: 3, column: 18, end_line: 3, end_column: 29, start: 48, end: 59 }),
+        // Only the first error in a string.
+        ("[line 4] Error at '\\u{D800}': Invalid escape sequence.".to_string(),
+            Span { line: 4, column: 19, end_line: 4, end_column: 27, start: 80, end: 88 }),
+        ("[line 4] Error at '\\u': Invalid escape sequence.".to_string(),
+            Span { line: 4, column: 42, end_line: 4, end_column: 44, start: 103, end: 105 }),
+    ]);
+
+    // The strings with errors still have a token.
+    let strings: Vec<&str> = token_list.iter()
+        .filter(|token| token.token_type() == TokenType::String)
+        .map(|token| token.lexeme())
+        .collect();
+    assert_eq!(strings, vec![r#""ok\n""#, r#""bad: \q""#, r#""too long: \u{1234567}""#, 
+        r#""surrogate: \u{D800}""#, r#""no brace: \u41""#, r#""after""#]);
+
+    // So only the escape errors are reported: the parser has none to follow.
+    assert!(Parser::new(&token_list).parse().is_ok());
+}
+
 #[test]
 // On author's https://github.com/munificent/craftinginterpreters/tree/master/test/
 //
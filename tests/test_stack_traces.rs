@@ -0,0 +1,99 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/stack_trace/`.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_stack_traces
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_stack_traces_interpreter -- --exact [--nocapture]
+//!     * cargo test test_stack_traces_vm -- --exact [--nocapture]
+//!
+
+mod test_common;
+
+use rlox::lox_error::LoxError;
+use rlox::resolver::Resolver;
+use crate::test_common::{
+    assert_parse_script_statements,
+    make_interpreter_byte_stream,
+    make_vm_byte_stream,
+};
+
+struct TestScriptAndTrace<'a> {
+    script_name: &'a str,
+    expected_trace: Vec<&'a str>,
+}
+
+fn get_stack_trace_scripts<'a>() -> Vec<TestScriptAndTrace<'a>> {
+    vec![
+        TestScriptAndTrace {
+            script_name: "./tests/data/stack_trace/nested_calls.lox",
+            expected_trace: vec![
+                "[line 2] in inner()",
+                "[line 7] in outer()",
+                "[line 10] in script",
+            ],
+        },
+        TestScriptAndTrace {
+            script_name: "./tests/data/stack_trace/native.lox",
+            expected_trace: vec![
+                "[native] in pop()",
+                "[line 2] in last()",
+                "[line 6] in script",
+            ],
+        },
+        TestScriptAndTrace {
+            script_name: "./tests/data/stack_trace/initializer.lox",
+            expected_trace: vec![
+                "[line 3] in init()",
+                "[line 7] in copy()",
+                "[line 13] in script",
+            ],
+        },
+        TestScriptAndTrace {
+            script_name: "./tests/data/stack_trace/caught.lox",
+            expected_trace: vec![
+                "[line 11] in script",
+            ],
+        },
+    ]
+}
+
+// Each script has exactly one uncaught runtime error.
+fn assert_trace(entry: &TestScriptAndTrace, err: LoxError) {
+    let errors = err.errors();
+    assert_eq!(errors.len(), 1, "Error in {}", entry.script_name);
+    assert_eq!(errors[0].trace(), entry.expected_trace, "Error in {}", entry.script_name);
+}
+
+#[test]
+fn test_stack_traces_interpreter() {
+    for entry in get_stack_trace_scripts() {
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        let mut interpreter = make_interpreter_byte_stream();
+        let mut resolver = Resolver::new(&mut interpreter);
+        assert!(resolver.resolve(&statements).is_ok(), "Error in {}", entry.script_name);
+
+        let err = interpreter.interpret(&statements).expect_err("Expected a runtime error");
+        assert_trace(&entry, err);
+    }
+}
+
+#[test]
+fn test_stack_traces_vm() {
+    for entry in get_stack_trace_scripts() {
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        let mut interpreter = make_interpreter_byte_stream();
+        let mut resolver = Resolver::new(&mut interpreter);
+        assert!(resolver.resolve(&statements).is_ok(), "Error in {}", entry.script_name);
+
+        let mut vm = make_vm_byte_stream();
+        let err = vm.interpret(&statements).expect_err("Expected a runtime error");
+        assert_trace(&entry, err);
+    }
+}
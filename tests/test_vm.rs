@@ -0,0 +1,156 @@
+// Date Created: 17/10/2026.
+
+//! Uses data from `./data/vm/`, and all other scripts in `./data/`.
+//!
+//! The bytecode VM is the book's clox part. Its output and its error 
+//! messages must be identical to the tree-walker's: 
+//! `test_vm_matches_interpreter` runs every script which resolves successfully 
+//! on both backends, and compares their output and errors.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_vm
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test test_vm_interpreter -- --exact [--nocapture]
+//!     * cargo test test_vm_matches_interpreter -- --exact [--nocapture]
+//!
+
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+mod test_common;
+use rlox::scanner::Scanner;
+use rlox::parser::Parser;
+use rlox::resolver::Resolver;
+use crate::test_common::{
+    get_script_contents,
+    make_interpreter_byte_stream,
+    extract_output_lines,
+    extract_error_lines,
+    make_vm_byte_stream,
+    extract_vm_output_lines,
+    split_expected_output,
+    assert_parse_script_statements,
+    TestScriptAndResult,
+    TestScriptAndResults,
+};
+
+// The benchmarks take too long on the tree-walker in debug builds. The sandbox 
+// scripts loop forever without their limits.
+const SKIPPED: [&str; 2] = [
+    "./tests/data/benchmark",
+    "./tests/data/sandbox",
+];
+
+fn get_vm_script_results<'a>() -> TestScriptAndResults<'a> {
+    vec![
+        TestScriptAndResult {
+            script_name: "./tests/data/vm/closures.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["0.0", "1.0", "2.0", "2.0", "changed"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/vm/try_finally.lox",
+            expected_result: true,
+            // Normalises f64.
+            expected_output: vec!["finally", "try", "0.0", "finally", "finally", "finally", 
+                "inner finally", "caught inner", "outer finally", "caught", 
+                "Operand must be a number."],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/vm/classes.lox",
+            expected_result: true,
+            expected_output: vec!["B, then A method of c", "extra", "again", "field", 
+                "B, then A method of b", "C", "C instance"],
+        },
+        TestScriptAndResult {
+            script_name: "./tests/data/super/extra_arguments.lox",
+            expected_result: false,
+            expected_output: vec!["Derived.foo()", 
+                "[line 10] Error at ')': Expected 2 arguments but got 4."],
+        },
+    ]
+} // cargo test test_vm_interpreter -- --exact [--nocapture]
+
+fn collect_scripts(dir: &Path, scripts: &mut Vec<PathBuf>) {
+    for entry in read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if SKIPPED.iter().any(|skipped| path == Path::new(skipped)) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_scripts(&path, scripts);
+        } else if path.extension().is_some_and(|ext| ext == "lox") {
+            scripts.push(path);
+        }
+    }
+}
+
+#[test]
+fn test_vm_interpreter() {
+    let test_scripts_and_results = get_vm_script_results();
+
+    for entry in test_scripts_and_results {
+        let statements = assert_parse_script_statements(entry.script_name);
+
+        let mut interpreter = make_interpreter_byte_stream();
+        let mut resolver = Resolver::new(&mut interpreter);
+        assert!(resolver.resolve(&statements).is_ok(), "Error in {}", entry.script_name);
+
+        let mut vm = make_vm_byte_stream();
+        let result = vm.interpret(&statements);
+
+        let errors = extract_error_lines(&result);
+        let (expected_lines, expected_errors) = split_expected_output(&entry.expected_output, &errors);
+        assert_eq!(result.is_ok(), entry.expected_result, "Error in {}", entry.script_name);
+        assert_eq!(extract_vm_output_lines(&vm), expected_lines, 
+            "Error in {}", entry.script_name);
+        assert_eq!(errors, expected_errors, "Error in {}", entry.script_name);
+    }
+}
+
+#[test]
+fn test_vm_matches_interpreter() {
+    let mut scripts: Vec<PathBuf> = vec![];
+    collect_scripts(Path::new("./tests/data"), &mut scripts);
+    scripts.sort();
+
+    let mut compared = 0;
+    for script in scripts {
+        let script_name = script.to_str().unwrap();
+        let contents = get_script_contents(script_name).unwrap();
+
+        // Scanner, parser and resolver errors are the same for both backends.
+        let Ok(tokens) = Scanner::new(&contents).scan_tokens() else { continue };
+        let Ok(statements) = Parser::new(&tokens).parse() else { continue };
+
+        // Both backends stop unbounded recursion at the same depth, well within 
+        // the stack of the test thread.
+        let mut interpreter = make_interpreter_byte_stream();
+        interpreter.set_script_path(script_name);
+        interpreter.set_max_call_depth(64);
+        let mut resolver = Resolver::new(&mut interpreter);
+        if resolver.resolve(&statements).is_err() {
+            continue;
+        }
+        let expected = interpreter.interpret(&statements);
+
+        let mut vm = make_vm_byte_stream();
+        vm.set_script_path(script_name);
+        vm.set_max_call_depth(64);
+        let result = vm.interpret(&statements);
+
+        assert_eq!(extract_vm_output_lines(&vm), extract_output_lines(&interpreter), 
+            "Error in {}", script_name);
+        assert_eq!(extract_error_lines(&result), extract_error_lines(&expected), 
+            "Error in {}", script_name);
+        assert_eq!(result.map_err(|err| err.to_string()), expected.map_err(|err| err.to_string()), 
+            "Error in {}", script_name);
+        compared += 1;
+    }
+
+    assert!(compared > 200, "Only {} scripts compared", compared);
+}
@@ -31,7 +31,7 @@ impl GenerateAst {
         types: &Vec<&str>
     ) -> Result<(), io::Error> {
         for t in types {
-            // t: "Assign   : Token name, Rc<Expr> value"
+            // t: "Assign   : Token name, Rc<Expr> value, Span span"
             let last_colon = t.rfind(':').unwrap();
             let (type_name, _) = t.split_at(last_colon);
             let trimmed_type = type_name.trim();
@@ -45,6 +45,24 @@ impl GenerateAst {
         Ok(())
     }
 
+    // Every node carries the source span it was parsed from.
+    fn span_pattern_matching_dispatch(file: &mut File, 
+        base_name: &str,
+        types: &Vec<&str>
+    ) -> Result<(), io::Error> {
+        for t in types {
+            // t: "Assign   : Token name, Rc<Expr> value, Span span"
+            let last_colon = t.rfind(':').unwrap();
+            let (type_name, _) = t.split_at(last_colon);
+
+            file.write_all(format!("            {0}::{1}(node) => node.span(),\n",
+                base_name, type_name.trim()).as_bytes()
+            )?;
+        }
+
+        Ok(())
+    }
+
     fn define_visitor(file: &mut File,
         base_name: &str,
         types: &Vec<&str>
@@ -82,7 +100,15 @@ impl GenerateAst {
         file.write_all(format!("        match {}.as_ref() {{\n", base_name.to_lowercase()).as_bytes())?;
         Self::accept_pattern_matching_dispatch(file, base_name, types)?;
         file.write_all("        }\n".as_bytes())?; // match self closing
-        file.write_all("    }\n".as_bytes())?; // pub fn accept() closing.
+        file.write_all("    }\n\n".as_bytes())?; // pub fn accept() closing.
+
+        // Not every binary uses the spans of both `Expr` and `Stmt`.
+        file.write_all("    #[allow(dead_code)]\n".as_bytes())?;
+        file.write_all("    pub fn span(&self) -> &Span {\n".as_bytes())?;
+        file.write_all("        match self {\n".as_bytes())?;
+        Self::span_pattern_matching_dispatch(file, base_name, types)?;
+        file.write_all("        }\n".as_bytes())?; // match self closing
+        file.write_all("    }\n".as_bytes())?; // pub fn span() closing.
 
         file.write_all("}\n".as_bytes())?;
 
@@ -207,39 +233,54 @@ fn main() {
     }
 
     let _ = GenerateAst::define_ast(vec!["use std::rc::Rc;\n\n",
-                "use super::token::{LiteralValue, Token};\n", 
+                "use super::token::{LiteralValue, Span, Token};\n", 
 	            "use super::lox_runtime_error::LoxRuntimeError;\n\n"], 
                 "Expr", &args[1], vec![
-				    "Assign   : Token name, Rc<Expr> value",
-                    "Binary   : Rc<Expr> left, Token operator, Rc<Expr> right",
-                    "Call     : Rc<Expr> callee, Token paren, Vec<Rc<Expr>> arguments",
-                    "Get      : Rc<Expr> object, Token name",
-                    "Grouping : Rc<Expr> expression",
-                    "Literal  : LiteralValue value",
-                    "Logical  : Rc<Expr> left, Token operator, Rc<Expr> right",
-                    "Set      : Rc<Expr> object, Token name, Rc<Expr> value",
-                    "Super    : Token keyword, Token method",
-                    "This     : Token keyword",
-                    "Unary    : Token operator, Rc<Expr> right",
-                    "Variable : Token name"
+				    "Assign   : Token name, Rc<Expr> value, Span span",
+                    "Binary   : Rc<Expr> left, Token operator, Rc<Expr> right, Span span",
+                    "Call     : Rc<Expr> callee, Token paren, Vec<Rc<Expr>> arguments, \
+                                Span span",
+                    "Get      : Rc<Expr> object, Token name, Span span",
+                    "Grouping : Rc<Expr> expression, Span span",
+                    "Index    : Rc<Expr> object, Token bracket, Rc<Expr> index, Span span",
+                    "IndexSet : Rc<Expr> object, Token bracket, Rc<Expr> index, \
+                                Rc<Expr> value, Span span",
+                    "List     : Vec<Rc<Expr>> elements, Span span",
+                    "Literal  : LiteralValue value, Span span",
+                    "Logical  : Rc<Expr> left, Token operator, Rc<Expr> right, Span span",
+                    "Map      : Token brace, Vec<Rc<Expr>> keys, Vec<Rc<Expr>> values, \
+                                Span span",
+                    "Set      : Rc<Expr> object, Token name, Rc<Expr> value, Span span",
+                    "Super    : Token keyword, Token method, Span span",
+                    "This     : Token keyword, Span span",
+                    "Unary    : Token operator, Rc<Expr> right, Span span",
+                    "Variable : Token name, Span span"
 				]);
 
     let _ = GenerateAst::define_ast(vec!["use std::rc::Rc;\n\n",
-                "use super::token::Token;\n",
+                "use super::token::{Span, Token};\n",
                 "use super::expr::Expr;\n",
 				"use super::lox_runtime_error::LoxRuntimeError;\n\n"], 
                 "Stmt", &args[1], vec![
-                    "Block      : Vec<Rc<Stmt>> statements",
+                    "Block      : Vec<Rc<Stmt>> statements, Span span",
+                    "Break      : Token keyword, Span span",
                     "Class      : Token name, Option<Rc<Expr>> superclass, \
-                                  Vec<Rc<Function>> methods",
-                    "Expression : Rc<Expr> expression",
+                                  Vec<Rc<Function>> methods, Span span",
+                    "Continue   : Token keyword, Span span",
+                    "Expression : Rc<Expr> expression, Span span",
                     "Function   : Token name, Vec<Token> params, \
-                                  Vec<Rc<Stmt>> body",
+                                  Vec<Rc<Stmt>> body, Span span",
                     "If         : Rc<Expr> condition, Rc<Stmt> then_branch, \
-                                  Option<Rc<Stmt>> else_branch",
-                    "Print      : Rc<Expr> expression",
-                    "Return     : Token keyword, Option<Rc<Expr>> value",
-                    "Var        : Token name, Option<Rc<Expr>> initializer",
-                    "While      : Rc<Expr> condition, Rc<Stmt> body"
+                                  Option<Rc<Stmt>> else_branch, Span span",
+                    "Import     : Token keyword, String path, Token name, Span span",
+                    "Print      : Rc<Expr> expression, Span span",
+                    "Return     : Token keyword, Option<Rc<Expr>> value, Span span",
+                    "Throw      : Token keyword, Rc<Expr> value, Span span",
+                    "Try        : Vec<Rc<Stmt>> body, Option<Token> catch_name, \
+                                  Option<Vec<Rc<Stmt>>> catch_body, \
+                                  Option<Vec<Rc<Stmt>>> finally_body, Span span",
+                    "Var        : Token name, Option<Rc<Expr>> initializer, Span span",
+                    "While      : Rc<Expr> condition, Rc<Stmt> body, \
+                                  Option<Rc<Expr>> increment, Span span"
 				]);
 }